@@ -0,0 +1,91 @@
+//! Measures how searching for props to pull scales with the number of actors
+//! and props. Run with `cargo bench --features testing`, and additionally with
+//! `--features parallel` to compare.
+//!
+//! Avian's own physics step is disabled once the props have settled, so only
+//! the pickup systems run in the measured ticks.
+
+use std::f32::consts::TAU;
+
+use avian_pickup::{prelude::*, testing::PickupTestApp};
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+const ACTORS: [usize; 3] = [1, 10, 100];
+const PROPS: [usize; 3] = [10, 100, 1000];
+
+/// Spawns actors in a circle looking at a grid of props in the middle.
+/// The props are too heavy to be pulled, so every actor searches for props
+/// in every tick without any of them ever being picked up.
+fn setup(actors: usize, props: usize) -> (PickupTestApp, Vec<Entity>) {
+    let mut app = PickupTestApp::new();
+    app.spawn_ground();
+    let side = (props as f32).sqrt().ceil() as usize;
+    for i in 0..props {
+        let (x, z) = ((i % side) as f32, (i / side) as f32);
+        let translation = Vec3::new(x - side as f32 / 2.0, 0.25, z - side as f32 / 2.0) * 0.6;
+        let prop = app.spawn_prop(translation);
+        app.world_mut().entity_mut(prop).insert(Mass(1000.0));
+    }
+    let actors = (0..actors)
+        .map(|i| {
+            let angle = i as f32 / actors as f32 * TAU;
+            let translation = Vec3::new(angle.cos() * 4.0, 1.0, angle.sin() * 4.0);
+            app.spawn_actor(
+                Transform::from_translation(translation).looking_at(Vec3::ZERO, Vec3::Y),
+                AvianPickupActor::default(),
+            )
+        })
+        .collect();
+    // Let the props settle.
+    app.step_ticks(10);
+    freeze_physics(&mut app);
+    (app, actors)
+}
+
+/// Stops Avian from stepping the simulation, so that a tick only runs the
+/// pickup systems against the already settled world.
+fn freeze_physics(app: &mut PickupTestApp) {
+    app.world_mut()
+        .schedule_scope(PhysicsSchedule, |_, schedule| {
+            schedule.configure_sets(
+                (
+                    PhysicsStepSystems::First,
+                    PhysicsStepSystems::BroadPhase,
+                    PhysicsStepSystems::NarrowPhase,
+                    PhysicsStepSystems::Solver,
+                    PhysicsStepSystems::Sleeping,
+                    PhysicsStepSystems::SpatialQuery,
+                    PhysicsStepSystems::Finalize,
+                    PhysicsStepSystems::Last,
+                )
+                    .run_if(|| false),
+            );
+        });
+}
+
+fn target_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("target_search");
+    for actors in ACTORS {
+        for props in PROPS {
+            let (mut app, actor_entities) = setup(actors, props);
+            group.bench_with_input(
+                BenchmarkId::new(format!("{actors}_actors"), props),
+                &props,
+                |b, _| {
+                    b.iter(|| {
+                        for &actor in &actor_entities {
+                            app.send(actor, AvianPickupAction::Pull);
+                        }
+                        app.step();
+                    });
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, target_search);
+criterion_main!(benches);
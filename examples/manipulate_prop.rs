@@ -3,10 +3,7 @@
 
 use std::f32::consts::FRAC_PI_2;
 
-use avian_pickup::{
-    prelude::*,
-    prop::{PreferredPickupDistanceOverride, PreferredPickupRotation},
-};
+use avian_pickup::prelude::*;
 use avian3d::prelude::*;
 use bevy::{
     color::palettes::tailwind,
@@ -32,11 +29,10 @@ fn main() {
         // to the last variable timestep schedule before the fixed timestep systems run.
         .add_systems(
             RunFixedMainLoop,
-            (accumulate_input, handle_pickup_input, rotate_camera)
+            (handle_pickup_input, rotate_camera)
                 .chain()
                 .in_set(RunFixedMainLoopSystems::BeforeFixedMainLoop),
         )
-        .add_systems(FixedUpdate, move_prop)
         .run();
 }
 
@@ -59,9 +55,18 @@ fn setup(
             // Increase the maximum distance a bit to show off the
             // prop changing its distance on scroll.
             interaction_distance: 15.0,
+            hold: AvianPickupActorHoldConfig {
+                // Grab props where they are and keep them at that distance,
+                // like the physics gun in Garry's Mod.
+                mode: AvianPickupHoldMode::TractorBeam,
+                ..default()
+            },
+            manipulation: AvianPickupActorManipulationConfig {
+                distance_range: 0.5..=15.0,
+                ..default()
+            },
             ..default()
         },
-        InputAccumulation::default(),
     ));
 
     commands.spawn((
@@ -93,8 +98,6 @@ fn setup(
         // All `RigidBody::Dynamic` entities are able to be picked up.
         RigidBody::Dynamic,
         Collider::from(box_shape),
-        PreferredPickupDistanceOverride::default(),
-        PreferredPickupRotation::default(),
         // Because we are moving the camera independently of the physics system,
         // interpolation is needed to prevent jittering.
         TransformInterpolation,
@@ -104,37 +107,56 @@ fn setup(
 /// Pass player input along to `avian_pickup`
 fn handle_pickup_input(
     mut avian_pickup_input_writer: MessageWriter<AvianPickupInput>,
-    key_input: Res<ButtonInput<MouseButton>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    key_input: Res<ButtonInput<KeyCode>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mouse_wheel: Res<AccumulatedMouseScroll>,
     actors: Query<Entity, With<AvianPickupActor>>,
 ) {
     for actor in &actors {
-        if key_input.just_pressed(MouseButton::Left) {
-            avian_pickup_input_writer.write(AvianPickupInput {
-                action: AvianPickupAction::Throw,
-                actor,
-            });
+        if mouse_input.just_pressed(MouseButton::Left) {
+            avian_pickup_input_writer.write(AvianPickupInput::new(actor, AvianPickupAction::Throw));
+        }
+        if mouse_input.just_pressed(MouseButton::Right) {
+            avian_pickup_input_writer.write(AvianPickupInput::new(actor, AvianPickupAction::Drop));
         }
-        if key_input.just_pressed(MouseButton::Right) {
-            avian_pickup_input_writer.write(AvianPickupInput {
-                action: AvianPickupAction::Drop,
+        if mouse_input.pressed(MouseButton::Right) {
+            avian_pickup_input_writer.write(AvianPickupInput::new(actor, AvianPickupAction::Pull));
+        }
+
+        // The factor is just an arbitrary scroll sensitivity value.
+        let scroll = mouse_wheel.delta.y * 0.25;
+        if scroll != 0.0 {
+            avian_pickup_input_writer.write(AvianPickupInput::new(
                 actor,
-            });
+                AvianPickupAction::AdjustDistance(scroll),
+            ));
         }
-        if key_input.pressed(MouseButton::Right) {
-            avian_pickup_input_writer.write(AvianPickupInput {
-                action: AvianPickupAction::Pull,
+
+        if is_shift_pressed(&key_input) && mouse_motion.delta != Vec2::ZERO {
+            // The factors are just arbitrary mouse sensitivity values.
+            // It's often nicer to have a faster horizontal sensitivity than vertical.
+            let mouse_sensitivity = Vec2::new(0.003, 0.002);
+            let delta = mouse_motion.delta * mouse_sensitivity;
+            avian_pickup_input_writer.write(AvianPickupInput::new(
                 actor,
-            });
+                AvianPickupAction::Rotate {
+                    yaw: delta.x,
+                    pitch: delta.y,
+                    roll: 0.0,
+                },
+            ));
         }
     }
 }
 
 fn rotate_camera(
     accumulated_mouse_motion: Res<AccumulatedMouseMotion>,
-    camera: Single<(&mut Transform, &InputAccumulation), With<Camera>>,
+    key_input: Res<ButtonInput<KeyCode>>,
+    mut camera: Single<&mut Transform, With<Camera>>,
 ) {
-    let (mut transform, input) = camera.into_inner();
-    if input.shift {
+    // While shift is pressed, the mouse rotates the prop instead.
+    if is_shift_pressed(&key_input) {
         return;
     }
 
@@ -145,91 +167,15 @@ fn rotate_camera(
     let delta_yaw = -delta.x * camera_sensitivity.x;
     let delta_pitch = -delta.y * camera_sensitivity.y;
 
-    let (yaw, pitch, roll) = transform.rotation.to_euler(EulerRot::YXZ);
+    let (yaw, pitch, roll) = camera.rotation.to_euler(EulerRot::YXZ);
     let yaw = yaw + delta_yaw;
 
     const PITCH_LIMIT: f32 = FRAC_PI_2 - 0.01;
     let pitch = (pitch + delta_pitch).clamp(-PITCH_LIMIT, PITCH_LIMIT);
 
-    transform.rotation = Quat::from_euler(EulerRot::YXZ, yaw, pitch, roll);
-}
-
-fn accumulate_input(
-    mouse_motion: Res<AccumulatedMouseMotion>,
-    mouse_wheel: Res<AccumulatedMouseScroll>,
-    key_input: Res<ButtonInput<KeyCode>>,
-    mut accumulation: Query<&mut InputAccumulation>,
-) {
-    for mut input in &mut accumulation {
-        // The factors are just arbitrary mouse sensitivity values.
-        // It's often nicer to have a faster horizontal sensitivity than vertical.
-        let mouse_sensitivity = Vec2::new(0.003, 0.002);
-        input.rotation += mouse_motion.delta * mouse_sensitivity;
-    }
-    for mut input in &mut accumulation {
-        const SCROLL_SENSITIVITY: f32 = 1.0;
-        let delta = mouse_wheel.delta.y * SCROLL_SENSITIVITY;
-        input.zoom += delta as i32;
-    }
-    for mut input in &mut accumulation {
-        input.shift =
-            key_input.pressed(KeyCode::ShiftLeft) || key_input.pressed(KeyCode::ShiftRight);
-    }
-}
-
-/// Systems in fixed timesteps may not run every frame,
-/// so we accumulate all input that happened since the last fixed update.
-#[derive(Debug, Component, Default)]
-struct InputAccumulation {
-    /// Accumulated mouse scrolling
-    zoom: i32,
-    /// Accumulated mouse motion
-    rotation: Vec2,
-    /// Was shift pressed during the last frame?
-    shift: bool,
+    camera.rotation = Quat::from_euler(EulerRot::YXZ, yaw, pitch, roll);
 }
 
-fn move_prop(
-    time: Res<Time>,
-    mut actors: Query<(&mut InputAccumulation, &Transform, &AvianPickupActorState)>,
-    mut props: Query<(
-        &mut PreferredPickupDistanceOverride,
-        &mut PreferredPickupRotation,
-    )>,
-) {
-    let dt = time.delta_secs();
-    for (mut input, transform, state) in &mut actors {
-        let AvianPickupActorState::Holding(prop) = state else {
-            continue;
-        };
-        let Ok((mut distance, mut rotation)) = props.get_mut(*prop) else {
-            error!("Prop entity was deleted or in an invalid state. Ignoring.");
-            continue;
-        };
-        const SCROLL_VELOCITY: f32 = 5.0;
-        let delta = input.zoom as f32 * SCROLL_VELOCITY * dt;
-        input.zoom = 0;
-
-        distance.0 += delta;
-        distance.0 = distance.0.clamp(0.5, 15.0);
-
-        if !input.shift {
-            continue;
-        }
-
-        let rotation_sensitivity = Vec2::new(10.0, 10.0);
-        // Make the yaw global
-        let y_rotation_global =
-            Quat::from_rotation_y(input.rotation.x * rotation_sensitivity.x * dt);
-
-        // Make the pitch relative to the actor's orientation
-        let horizontal_axis = transform.right().into();
-        let vertical_rotation = Quat::from_axis_angle(
-            horizontal_axis,
-            input.rotation.y * rotation_sensitivity.y * dt,
-        );
-        rotation.0 = vertical_rotation * y_rotation_global * rotation.0;
-
-        input.rotation = Vec2::ZERO;
-    }
+fn is_shift_pressed(key_input: &ButtonInput<KeyCode>) -> bool {
+    key_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
 }
@@ -91,22 +91,13 @@ fn handle_input(
 ) {
     for actor in &actors {
         if key_input.just_pressed(MouseButton::Left) {
-            avian_pickup_input_writer.write(AvianPickupInput {
-                action: AvianPickupAction::Throw,
-                actor,
-            });
+            avian_pickup_input_writer.write(AvianPickupInput::new(actor, AvianPickupAction::Throw));
         }
         if key_input.just_pressed(MouseButton::Right) {
-            avian_pickup_input_writer.write(AvianPickupInput {
-                action: AvianPickupAction::Drop,
-                actor,
-            });
+            avian_pickup_input_writer.write(AvianPickupInput::new(actor, AvianPickupAction::Drop));
         }
         if key_input.pressed(MouseButton::Right) {
-            avian_pickup_input_writer.write(AvianPickupInput {
-                action: AvianPickupAction::Pull,
-                actor,
-            });
+            avian_pickup_input_writer.write(AvianPickupInput::new(actor, AvianPickupAction::Pull));
         }
     }
 }
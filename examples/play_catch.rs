@@ -194,22 +194,14 @@ fn handle_input(
 ) {
     for player in &players {
         if key_input.just_pressed(MouseButton::Left) {
-            avian_pickup_input_writer.write(AvianPickupInput {
-                action: AvianPickupAction::Throw,
-                actor: player,
-            });
+            avian_pickup_input_writer
+                .write(AvianPickupInput::new(player, AvianPickupAction::Throw));
         }
         if key_input.just_pressed(MouseButton::Right) {
-            avian_pickup_input_writer.write(AvianPickupInput {
-                action: AvianPickupAction::Drop,
-                actor: player,
-            });
+            avian_pickup_input_writer.write(AvianPickupInput::new(player, AvianPickupAction::Drop));
         }
         if key_input.pressed(MouseButton::Right) {
-            avian_pickup_input_writer.write(AvianPickupInput {
-                action: AvianPickupAction::Pull,
-                actor: player,
-            });
+            avian_pickup_input_writer.write(AvianPickupInput::new(player, AvianPickupAction::Pull));
         }
     }
 }
@@ -268,10 +260,7 @@ fn make_npc_catch(
         if !matches!(npc.state, NpcState::Catching) {
             continue;
         }
-        avian_pickup_input_writer.write(AvianPickupInput {
-            action: AvianPickupAction::Pull,
-            actor: entity,
-        });
+        avian_pickup_input_writer.write(AvianPickupInput::new(entity, AvianPickupAction::Pull));
     }
 }
 
@@ -304,10 +293,7 @@ fn on_aim_timer(
             continue;
         }
         npc.waiting();
-        avian_pickup_input_writer.write(AvianPickupInput {
-            action: AvianPickupAction::Throw,
-            actor: entity,
-        });
+        avian_pickup_input_writer.write(AvianPickupInput::new(entity, AvianPickupAction::Throw));
     }
 }
 
@@ -2,21 +2,42 @@
 
 use std::ops::RangeInclusive;
 
-use avian3d::{math::Scalar, prelude::*};
+use avian3d::{
+    math::{AdjustPrecision as _, AsF32 as _, Quaternion, Scalar, Vector},
+    prelude::*,
+};
+use bevy_ecs::entity::MapEntities;
 
 use crate::{
-    interaction::{HoldError, ShadowParams},
+    interaction::{ActorVelocity, HoldError, HoldManipulation, ShadowParams},
+    math::up_frame,
     prelude::*,
 };
 
 pub(super) mod prelude {
     pub use super::{
-        AvianPickupActor, AvianPickupActorHoldConfig, AvianPickupActorPullConfig,
-        AvianPickupActorState, AvianPickupActorThrowConfig,
+        AimRayOverride, AvianPickupActor, AvianPickupActorAttributionConfig,
+        AvianPickupActorHoldConfig, AvianPickupActorManipulationConfig,
+        AvianPickupActorPlaceConfig, AvianPickupActorPullConfig, AvianPickupActorState,
+        AvianPickupActorThrowConfig, AvianPickupHoldMode, AvianPickupRotationSnapping,
+        AvianPickupRotationSpace, AvianPickupThrowSpin, AvianPickupUpDirection, HoldTargetOverride,
+        PickupAimOrigin, PickupHoldAnchor, PickupUpDirection, PlacementCandidate, PlacementError,
+        PropInventory, PropInventoryEntry, TractorBeamLength,
     };
 }
 
-pub(super) fn plugin(_app: &mut App) {}
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<AvianPickupActor>()
+        .register_type::<AvianPickupActorState>()
+        .register_type::<TractorBeamLength>()
+        .register_type::<PickupUpDirection>()
+        .register_type::<HoldTargetOverride>()
+        .register_type::<PickupAimOrigin>()
+        .register_type::<AimRayOverride>()
+        .register_type::<PickupHoldAnchor>()
+        .register_type::<PlacementCandidate>()
+        .register_type::<PropInventory>();
+}
 
 /// Tag component for an actor that is able to pick up object.
 /// For a first-person game, add this to the camera entity that is under the
@@ -24,6 +45,17 @@ pub(super) fn plugin(_app: &mut App) {}
 ///
 /// Requires the entity to also hold a [`Transform`] and [`GlobalTransform`].
 ///
+/// # Units
+///
+/// All lengths, linear speeds and linear impulses in this configuration are
+/// scaled by Avian's [`PhysicsLengthUnit`], just like Avian's own
+/// [`SleepingThreshold`]. The defaults given in meters are thus correct for
+/// any scene that configures its length unit, e.g. the default
+/// [`AvianPickupActorHoldConfig::preferred_distance`] of 0.6 m means 60.0
+/// world units when the length unit is 100.0.
+/// The same goes for the overrides in [`avian_pickup::prop`](crate::prop),
+/// [`AvianPickupAction::AdjustDistance`] and [`PropSocket::capture_radius`].
+///
 /// # Example
 ///
 /// ```
@@ -45,11 +77,19 @@ pub(super) fn plugin(_app: &mut App) {}
     derive(serde::Serialize, serde::Deserialize),
     reflect(Serialize, Deserialize)
 )]
-#[require(AvianPickupActorState, Cooldown, HoldError, ShadowParams)]
+#[require(
+    AvianPickupActorState,
+    Cooldown,
+    HoldError,
+    ShadowParams,
+    HoldManipulation,
+    ActorVelocity
+)]
 pub struct AvianPickupActor {
     /// The spatial query filter to use when looking for objects to pick up.\
     /// Note that no matter what this filter says, only entities with a
-    /// [`RigidBody::Dynamic`] will be considered in the first place.\
+    /// [`RigidBody::Dynamic`] or a removable [`SocketedProp`] will be
+    /// considered in the first place.\
     ///
     /// Default: Include all entities
     pub prop_filter: SpatialQueryFilter,
@@ -61,7 +101,9 @@ pub struct AvianPickupActor {
     /// actor.\
     /// This is used to filter out colliders that should not be
     /// taken into account when calculating the actor's total rigid body
-    /// extent.\
+    /// extent. The colliders of the actor's rigid body that pass this filter
+    /// also never block the line of sight from a [`PickupHoldAnchor`] to a
+    /// prop.\
     /// Default: Include all entities
     pub actor_filter: SpatialQueryFilter,
     /// How far away an object can be interacted with.\
@@ -75,13 +117,20 @@ pub struct AvianPickupActor {
     /// Default: 0.97
     ///
     /// Corresponds to Source's [`physcannon_cone`](https://developer.valvesoftware.com/wiki/Weapon_physcannon#physcannon_cone).
-    pub interaction_cone: f32,
+    pub interaction_cone: Scalar,
     /// Configuration that is only used when pulling props to the actor.
     pub pull: AvianPickupActorPullConfig,
     /// Configuration that is only used while holding props.
     pub hold: AvianPickupActorHoldConfig,
     /// Configuration that is only used when throwing props.
     pub throw: AvianPickupActorThrowConfig,
+    /// Configuration that is only used when manipulating held props.
+    pub manipulation: AvianPickupActorManipulationConfig,
+    /// Configuration that is only used when placing held props.
+    pub place: AvianPickupActorPlaceConfig,
+    /// Configuration for the [`LastHandledBy`] component inserted on props
+    /// released by this actor.
+    pub attribution: AvianPickupActorAttributionConfig,
 }
 
 /// Configuration that is only used when pulling props to the actor.
@@ -126,10 +175,15 @@ impl Default for AvianPickupActorPullConfig {
     reflect(Serialize, Deserialize)
 )]
 pub struct AvianPickupActorHoldConfig {
+    /// How the distance between the actor and the held prop is determined.\
+    /// Default: [`AvianPickupHoldMode::Carry`]
+    pub mode: AvianPickupHoldMode,
     /// The maximum distance between the player and the object when it can be
     /// picked up.\
     /// "distance" in this context is the distance between the edge of the prop
     /// and the origin of the actor.\
+    /// Ignored when using [`AvianPickupHoldMode::TractorBeam`] or
+    /// [`AvianPickupHoldMode::GroundPlane`].\
     /// Default: 3.0 m
     ///
     /// Corresponds to Source's [`physcannon_tracelength`](https://developer.valvesoftware.com/wiki/Weapon_physcannon#physcannon_tracelength)
@@ -163,7 +217,13 @@ pub struct AvianPickupActorHoldConfig {
     /// Can be overridden by adding a
     /// [`PitchRangeOverride`] to the prop.\
     /// Default: (-75.0).to_radians() to 75.0.to_radians()
-    pub pitch_range: RangeInclusive<f32>,
+    pub pitch_range: RangeInclusive<Scalar>,
+    /// Which direction is up for the actor. The yaw and pitch of the actor,
+    /// the pitch range, rotation snapping and placement are all relative to
+    /// this.\
+    /// Can be overridden by adding a [`PickupUpDirection`] to the actor.\
+    /// Default: [`AvianPickupUpDirection::Fixed`] with [`Dir3::Y`]
+    pub up_direction: AvianPickupUpDirection,
     /// The distance in meters between the player and the object when
     /// picked up and there is no obstacle in the way.\
     /// "distance" in this context is the distance between the edge of the prop
@@ -171,8 +231,17 @@ pub struct AvianPickupActorHoldConfig {
     /// Can be overridden by adding a
     /// [`PreferredPickupDistanceOverride`]
     /// to the prop.\
+    /// Ignored when using [`AvianPickupHoldMode::TractorBeam`] or
+    /// [`AvianPickupHoldMode::GroundPlane`].\
     /// Default: 0.6 m
     pub preferred_distance: Scalar,
+    /// The height above the ground plane at which props held with
+    /// [`AvianPickupHoldMode::GroundPlane`] are kept.
+    /// The ground plane goes through the world's origin and is perpendicular
+    /// to the [`up_direction`](Self::up_direction).\
+    /// Ignored when using any other mode.\
+    /// Default: 1.0 m
+    pub ground_plane_height: Scalar,
     /// The mass in kg of the object when picked up.
     /// This mechanism is needed because the held object's velocity is
     /// set directly, independent of its mass. This means that heavy
@@ -184,22 +253,321 @@ pub struct AvianPickupActorHoldConfig {
     /// [`PickupMassOverride`] to the prop.\
     /// Default: 1 kg
     pub temporary_prop_mass: Scalar,
+    /// If set, the held prop's rotation will snap to the coordinate axes of
+    /// the actor's heading when it gets close to them. These are the
+    /// [`up_direction`](Self::up_direction) and the direction the actor faces
+    /// along the ground. Useful for building games.\
+    /// Independent of this setting, [`AvianPickupAction::SnapRotation`] can
+    /// always be used to square up the held prop.\
+    /// Can be overridden by adding a
+    /// [`RotationSnappingOverride`] to the prop.\
+    /// Default: `None`
+    pub rotation_snapping: Option<AvianPickupRotationSnapping>,
 }
 
 impl Default for AvianPickupActorHoldConfig {
     fn default() -> Self {
         Self {
+            mode: default(),
             distance_to_allow_holding: 3.0,
             min_distance: 0.2,
             linear_velocity_easing: 1.0,
             angular_velocity_easing: 1.6,
-            pitch_range: (-75.0_f32).to_radians()..=75.0_f32.to_radians(),
+            pitch_range: Scalar::to_radians(-75.0)..=Scalar::to_radians(75.0),
+            up_direction: default(),
             preferred_distance: 0.6,
+            ground_plane_height: 1.0,
             temporary_prop_mass: 1.0,
+            rotation_snapping: None,
+        }
+    }
+}
+
+/// Configuration for snapping the rotation of a held prop to the coordinate
+/// axes of the actor's heading.
+/// Used in [`AvianPickupActorHoldConfig::rotation_snapping`].
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct AvianPickupRotationSnapping {
+    /// The maximum angle in radians between the prop's rotation and the
+    /// nearest snapped rotation for the snapping to kick in.\
+    /// Default: 30.0.to_radians()
+    ///
+    /// Corresponds to the hardcoded threshold of Source's `AlignAngles`.
+    pub threshold: Scalar,
+    /// The yaw, pitch and roll of a snapped rotation are multiples of this
+    /// angle in radians.\
+    /// Default: 90.0.to_radians()
+    pub increment: Scalar,
+}
+
+impl Default for AvianPickupRotationSnapping {
+    fn default() -> Self {
+        Self {
+            threshold: Scalar::to_radians(30.0),
+            increment: Scalar::to_radians(90.0),
+        }
+    }
+}
+
+/// Determines how far away from the actor a held prop is kept.
+/// Used in [`AvianPickupActorHoldConfig::mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+#[reflect(Debug, Default, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum AvianPickupHoldMode {
+    /// Props are pulled towards the actor until they are within
+    /// [`AvianPickupActorHoldConfig::distance_to_allow_holding`] and are then
+    /// held at [`AvianPickupActorHoldConfig::preferred_distance`].\
+    /// This emulates picking things up with your hands or with Half Life 2's
+    /// gravity gun.
+    #[default]
+    Carry,
+    /// Props are latched onto right where they are found, up to
+    /// [`AvianPickupActor::interaction_distance`] away, and are kept at that
+    /// distance while being held.\
+    /// The current distance is stored in a [`TractorBeamLength`] on the actor,
+    /// which you can mutate to move the prop closer or further away.\
+    /// This emulates the physics gun in Garry's Mod.
+    TractorBeam,
+    /// Props are latched onto right where they are found, up to
+    /// [`AvianPickupActor::interaction_distance`] away, and are kept
+    /// [`AvianPickupActorHoldConfig::ground_plane_height`] above the ground
+    /// plane, right where the actor aims at it.\
+    /// This is meant for top-down and strategy games, where the actor usually
+    /// aims with an [`AimRayOverride`] through the cursor.
+    GroundPlane,
+}
+
+/// The distance at which a prop held with [`AvianPickupHoldMode::TractorBeam`]
+/// is kept.
+/// "distance" in this context is the distance between the edge of the prop
+/// and the origin of the actor.\
+/// This component is automatically inserted on the actor when it starts
+/// holding a prop and removed again when it stops. Mutate it to change the
+/// length of the beam. It is clamped to
+/// [`AvianPickupActor::interaction_distance`].
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct TractorBeamLength(pub Scalar);
+
+/// Where an actor gets its up direction from.
+/// Used in [`AvianPickupActorHoldConfig::up_direction`].
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum AvianPickupUpDirection {
+    /// Up is always the given direction.
+    Fixed(Dir3),
+    /// Up is opposite to Avian's [`Gravity`]. Falls back to [`Dir3::Y`] when
+    /// there is no gravity.
+    Gravity,
+}
+
+impl Default for AvianPickupUpDirection {
+    fn default() -> Self {
+        Self::Fixed(Dir3::Y)
+    }
+}
+
+impl AvianPickupUpDirection {
+    /// Returns the up direction for the given `gravity`.
+    pub fn resolve(self, gravity: &Gravity) -> Dir3 {
+        match self {
+            Self::Fixed(up) => up,
+            Self::Gravity => Dir3::new(-gravity.0.f32()).unwrap_or(Dir3::Y),
+        }
+    }
+}
+
+/// The up direction of an actor, overriding
+/// [`AvianPickupActorHoldConfig::up_direction`].\
+/// Useful for actors whose up direction changes all the time, e.g. when
+/// walking on walls or on a spherical planet. Update it whenever the actor's
+/// up direction changes.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PickupUpDirection(pub Dir3);
+
+impl PickupUpDirection {
+    /// Returns the up direction of an actor with the given `config`, taking
+    /// an optional [`PickupUpDirection`] on the actor into account.
+    pub(crate) fn resolve(
+        up_direction: Option<&Self>,
+        config: &AvianPickupActor,
+        gravity: &Gravity,
+    ) -> Dir3 {
+        up_direction.map_or_else(|| config.hold.up_direction.resolve(gravity), |up| up.0)
+    }
+}
+
+/// Where an actor holds its prop, overriding the target that is otherwise
+/// derived from the actor's [`GlobalTransform`].\
+/// Useful when the prop should follow something other than the actor's line
+/// of sight, e.g. a VR controller or the hand of an animated character.
+/// The prop is held with its center of mass at the target's translation and
+/// keeps the rotation relative to the target that it had when it was picked
+/// up. [`AvianPickupActorHoldConfig::pitch_range`], the preferred distance
+/// and the [`TractorBeamLength`] have no effect while this is present.
+///
+/// Everything else works as usual: the prop's mass is swapped, it is dropped
+/// when it gets stuck, it is driven by its velocity, and it is kept out of
+/// obstacles between the actor and the target.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum HoldTargetOverride {
+    /// Hold the prop at this pose in world space. Update it whenever the
+    /// target moves.
+    Transform(Transform),
+    /// Hold the prop at the [`GlobalTransform`] of this entity, e.g. a hand.
+    /// Ignored while the entity does not exist.
+    Entity(#[entities] Entity),
+}
+
+impl HoldTargetOverride {
+    /// Returns the pose to hold the prop at, or `None` if the target entity
+    /// does not exist.
+    pub(crate) fn resolve(&self, q_transform: &Query<&GlobalTransform>) -> Option<Transform> {
+        match *self {
+            Self::Transform(transform) => Some(transform),
+            Self::Entity(entity) => q_transform
+                .get(entity)
+                .ok()
+                .map(GlobalTransform::compute_transform),
         }
     }
 }
 
+/// The entity an actor aims with, e.g. the camera in a third-person game.\
+/// Its [`GlobalTransform`] is used instead of the actor's to find props to
+/// pull and unfreeze, to place props, and as the direction props are thrown
+/// and held in. Distances like [`AvianPickupActor::interaction_distance`] are
+/// measured from it.\
+/// Falls back to the actor's own [`GlobalTransform`] while the entity does
+/// not exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PickupAimOrigin(#[entities] pub Entity);
+
+impl PickupAimOrigin {
+    /// Returns the transform an actor aims with, taking an optional
+    /// [`AimRayOverride`] and [`PickupAimOrigin`] on the actor into account.
+    pub(crate) fn resolve(
+        aim_origin: Option<&Self>,
+        aim_ray: Option<&AimRayOverride>,
+        actor_transform: &GlobalTransform,
+        up: Dir3,
+        q_transform: &Query<&GlobalTransform>,
+    ) -> Transform {
+        let transform = aim_origin
+            .and_then(|aim_origin| q_transform.get(aim_origin.0).ok())
+            .unwrap_or(actor_transform)
+            .compute_transform();
+        aim_ray.map_or(transform, |aim_ray| {
+            aim_ray.transform(up, transform.rotation)
+        })
+    }
+}
+
+/// The ray an actor aims along, e.g. the ray through the cursor in a top-down
+/// or strategy game.\
+/// Takes precedence over both the actor's [`GlobalTransform`] and a
+/// [`PickupAimOrigin`] for everything the actor aims at, i.e. finding props
+/// to pull and unfreeze, placing props and throwing them.
+/// Update it every frame, e.g. with the result of
+/// `Camera::viewport_to_world`.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct AimRayOverride(pub Ray3d);
+
+impl AimRayOverride {
+    /// Returns the transform looking along the ray, kept upright relative to
+    /// `up`. When aiming straight along `up`, the top of the aim points where
+    /// an actor with the given `rotation` is heading instead.
+    pub(crate) fn transform(&self, up: Dir3, rotation: Quat) -> Transform {
+        let direction = self.0.direction;
+        let up_hint = if direction.cross(up.as_vec3()).length_squared() > 1e-6 {
+            up.as_vec3()
+        } else {
+            let heading = up_frame(up, rotation.adjust_precision()) * Vector::NEG_Z;
+            heading.f32() * -direction.dot(up.as_vec3()).signum()
+        };
+        Transform::from_translation(self.0.origin).looking_to(direction, up_hint)
+    }
+}
+
+/// The entity an actor holds props at, e.g. the hand or chest of a character
+/// in a third-person game.\
+/// Props are held in front of its translation, in the direction the actor
+/// aims in, and are kept out of obstacles between it and the prop.
+/// Props can only be picked up if nothing blocks the line of sight between it
+/// and the prop, so that a camera looking around a corner cannot reach behind
+/// walls.\
+/// Falls back to the actor's own translation while the entity does not exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PickupHoldAnchor(#[entities] pub Entity);
+
+impl PickupHoldAnchor {
+    /// Returns the transform props are held relative to: the translation of
+    /// an optional [`PickupHoldAnchor`] on the actor, falling back to the
+    /// actor's, combined with the rotation of the `aim` transform.
+    pub(crate) fn resolve(
+        hold_anchor: Option<&Self>,
+        aim: Transform,
+        actor_transform: &GlobalTransform,
+        q_transform: &Query<&GlobalTransform>,
+    ) -> Transform {
+        let anchor = hold_anchor
+            .and_then(|hold_anchor| q_transform.get(hold_anchor.0).ok())
+            .unwrap_or(actor_transform);
+        aim.with_translation(anchor.translation())
+    }
+}
+
 /// Configuration that is only used when throwing props.
 /// Used in [`AvianPickupActor::throw`].
 #[derive(Debug, Clone, PartialEq, Reflect)]
@@ -226,11 +594,40 @@ pub struct AvianPickupActorThrowConfig {
     /// Default: 0.0 m/s to 5.0 m/s
     pub linear_speed_range: RangeInclusive<Scalar>,
     /// The range of angular speeds in rad/s that the object can be thrown with.
-    /// When throwing, a random value in this range will be chosen.\
+    /// When throwing with [`AvianPickupThrowSpin::Random`], a random value in
+    /// this range will be chosen.\
     /// Can be overridden by adding a
     /// [`ThrownAngularSpeedOverride`] to the prop.\
     /// Default: 0.0 rad/s to 1.0 rad/s
     pub angular_speed_range: RangeInclusive<Scalar>,
+    /// How thrown props spin.
+    /// Can be overridden by adding a [`ThrowSpinOverride`] to the prop.\
+    /// Default: [`AvianPickupThrowSpin::Random`]
+    pub spin: AvianPickupThrowSpin,
+    /// Whether to insert a [`ThrownProp`] on thrown and punted props, which sends a
+    /// [`ThrownPropImpact`](crate::prelude::ThrownPropImpact) whenever the
+    /// prop hits something during its flight.\
+    /// Default: `false`
+    pub track_impacts: bool,
+    /// How many seconds after being thrown a prop's impacts are tracked at
+    /// most when [`AvianPickupActorThrowConfig::track_impacts`] is enabled.
+    /// Tracking also stops earlier when the prop comes to rest.\
+    /// Default: 5.0 s
+    pub impact_tracking_timeout: f32,
+    /// How much of the actor's own linear velocity is added to the velocity of
+    /// thrown props. Set this to 1.0 to throw props relative to a moving
+    /// player or vehicle.\
+    /// Uses the velocity of the actor's rigid body, or of the closest ancestor
+    /// that has one. If there is none, the velocity is estimated from how
+    /// the actor's transform changed since the last physics update.\
+    /// Default: 0.0
+    pub velocity_inheritance: Scalar,
+    /// Whether the velocity inherited through
+    /// [`AvianPickupActorThrowConfig::velocity_inheritance`] also includes
+    /// the tangential velocity at the prop's position caused by the actor
+    /// rotating, e.g. when throwing from a spinning carousel.\
+    /// Default: `false`
+    pub inherit_angular_velocity: bool,
 }
 
 impl Default for AvianPickupActorThrowConfig {
@@ -239,10 +636,336 @@ impl Default for AvianPickupActorThrowConfig {
             cutoff_mass_for_slowdown: 20.0,
             linear_speed_range: 0.0..=5.0,
             angular_speed_range: 0.0..=1.0,
+            spin: default(),
+            track_impacts: false,
+            impact_tracking_timeout: 5.0,
+            velocity_inheritance: 0.0,
+            inherit_angular_velocity: false,
+        }
+    }
+}
+
+/// Determines how thrown props spin.
+/// Used in [`AvianPickupActorThrowConfig::spin`].
+#[derive(Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum AvianPickupThrowSpin {
+    /// Spin around a random axis with a random speed from
+    /// [`AvianPickupActorThrowConfig::angular_speed_range`], or the speed of
+    /// a [`ThrownAngularSpeedOverride`] if present.\
+    /// This is what Half Life 2 does.
+    #[default]
+    Random,
+    /// Spin around a fixed axis in the actor's local space,
+    /// e.g. [`Vector::NEG_Z`] for a spiraling football.
+    ActorAxis {
+        /// The axis to spin around. Does not need to be normalized.
+        axis: Vector,
+        /// The angular speed in rad/s. Negative values spin the other way.
+        speed: Scalar,
+    },
+    /// Spin around a fixed axis in the prop's local space,
+    /// e.g. [`Vector::Y`] for a frisbee.
+    PropAxis {
+        /// The axis to spin around. Does not need to be normalized.
+        axis: Vector,
+        /// The angular speed in rad/s. Negative values spin the other way.
+        speed: Scalar,
+    },
+    /// Spin backwards relative to the throw direction, i.e. the top of the
+    /// prop moves towards the actor. Contains the angular speed in rad/s.
+    Backspin(Scalar),
+    /// Spin forwards relative to the throw direction, i.e. the top of the
+    /// prop moves away from the actor. Contains the angular speed in rad/s.
+    Topspin(Scalar),
+    /// Keep the angular velocity the prop had while being held.
+    Preserve,
+}
+
+/// Configuration that is only used when manipulating held props through
+/// [`AvianPickupAction::Rotate`] and [`AvianPickupAction::AdjustDistance`].
+/// Used in [`AvianPickupActor::manipulation`].\
+/// All manipulation is reset when the prop is no longer held.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct AvianPickupActorManipulationConfig {
+    /// Factor by which the angles of [`AvianPickupAction::Rotate`] are
+    /// multiplied.\
+    /// Default: 1.0
+    pub rotation_sensitivity: Scalar,
+    /// Factor by which the distance of [`AvianPickupAction::AdjustDistance`]
+    /// is multiplied.\
+    /// Default: 1.0
+    pub distance_sensitivity: Scalar,
+    /// The range in which [`AvianPickupAction::AdjustDistance`] can move the
+    /// held prop.
+    /// "distance" in this context is the distance between the edge of the prop
+    /// and the origin of the actor.\
+    /// Default: 0.2 m to 5.0 m
+    pub distance_range: RangeInclusive<Scalar>,
+    /// The space in which [`AvianPickupAction::Rotate`] is applied.\
+    /// Default: [`AvianPickupRotationSpace::WorldYaw`]
+    pub rotation_space: AvianPickupRotationSpace,
+}
+
+impl Default for AvianPickupActorManipulationConfig {
+    fn default() -> Self {
+        Self {
+            rotation_sensitivity: 1.0,
+            distance_sensitivity: 1.0,
+            distance_range: 0.2..=5.0,
+            rotation_space: default(),
+        }
+    }
+}
+
+/// The space in which [`AvianPickupAction::Rotate`] is applied.
+/// Used in [`AvianPickupActorManipulationConfig::rotation_space`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+#[reflect(Debug, Default, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum AvianPickupRotationSpace {
+    /// Yaw, pitch and roll are all relative to the actor's point of view.
+    Actor,
+    /// Yaw rotates the prop around the actor's
+    /// [up direction](AvianPickupActorHoldConfig::up_direction), like spinning
+    /// it on a turntable. Pitch and roll are relative to the actor's point of
+    /// view.
+    #[default]
+    WorldYaw,
+}
+
+/// Configuration that is only used when placing held props through
+/// [`AvianPickupAction::Place`].
+/// Used in [`AvianPickupActor::place`].
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct AvianPickupActorPlaceConfig {
+    /// How far away from the actor a prop can be placed.\
+    /// Default: 3.0 m
+    pub max_distance: Scalar,
+    /// The maximum angle in radians between a surface's normal and the actor's
+    /// [up direction](AvianPickupActorHoldConfig::up_direction) for a prop to
+    /// be placed on it.\
+    /// Default: 45.0.to_radians()
+    pub max_surface_angle: Scalar,
+    /// Whether the prop's up axis should be aligned with the normal of the
+    /// surface it is placed on.\
+    /// Default: `true`
+    pub align_to_surface: bool,
+    /// Whether to keep a [`PlacementCandidate`] on the actor up to date
+    /// while it is holding a prop, e.g. for rendering a ghost of the prop
+    /// where it would be placed.\
+    /// Default: `false`
+    pub preview: bool,
+}
+
+impl Default for AvianPickupActorPlaceConfig {
+    fn default() -> Self {
+        Self {
+            max_distance: 3.0,
+            max_surface_angle: Scalar::to_radians(45.0),
+            align_to_surface: true,
+            preview: false,
+        }
+    }
+}
+
+/// Where the held prop would be placed by [`AvianPickupAction::Place`].
+/// Inserted on the actor while it is holding a prop if
+/// [`AvianPickupActorPlaceConfig::preview`] is enabled.
+/// Use this to e.g. render a ghost of the prop.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PlacementCandidate {
+    /// The held prop.
+    #[entities]
+    pub prop: Entity,
+    /// The global transform the prop would be placed at.
+    /// `None` if no suitable surface was found.
+    pub transform: Option<Transform>,
+    /// Why the prop cannot be placed at [`Self::transform`].
+    /// `None` if the prop can be placed.
+    pub error: Option<PlacementError>,
+}
+
+impl PlacementCandidate {
+    /// Returns `true` if the prop can be placed at [`Self::transform`].
+    pub fn is_valid(&self) -> bool {
+        self.transform.is_some() && self.error.is_none()
+    }
+}
+
+/// The reason why a prop could not be placed.
+/// Used in [`PlacementCandidate`] and
+/// [`PropPlacementFailed`](crate::prelude::PropPlacementFailed).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum PlacementError {
+    /// There is no surface within
+    /// [`AvianPickupActorPlaceConfig::max_distance`] in front of the actor.
+    NoSurface,
+    /// The surface is steeper than
+    /// [`AvianPickupActorPlaceConfig::max_surface_angle`].
+    TooSteep,
+    /// The prop would bump into something it cannot be placed on before
+    /// reaching the surface, e.g. a wall next to it.
+    Obstructed,
+}
+
+/// Configuration for how long props remember who released them.
+/// Used in [`AvianPickupActor::attribution`].
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct AvianPickupActorAttributionConfig {
+    /// How many seconds after being released a prop keeps its
+    /// [`LastHandledBy`]. `None` means that it is never removed because of
+    /// its age.\
+    /// Default: `Some(5.0)` s
+    pub timeout: Option<f32>,
+    /// Whether to remove [`LastHandledBy`] as soon as the prop comes to rest,
+    /// i.e. when Avian puts it to [`Sleeping`].\
+    /// Default: `true`
+    pub clear_on_rest: bool,
+}
+
+impl Default for AvianPickupActorAttributionConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Some(5.0),
+            clear_on_rest: true,
+        }
+    }
+}
+
+/// Inventory that an [`AvianPickupActor`] can stash held props into through
+/// [`AvianPickupAction::Stash`](crate::prelude::AvianPickupAction::Stash)
+/// and retrieve them from through
+/// [`AvianPickupAction::Retrieve`](crate::prelude::AvianPickupAction::Retrieve).
+/// Add this to an actor to enable stashing.\
+/// Stashed props are removed from the physics world by disabling their rigid
+/// body and colliders. With the `visibility` feature, which is enabled by
+/// default, they are also hidden if they have a `Visibility`.
+/// They are marked with a [`StashedProp`] while in the inventory.
+#[derive(Debug, Component, Clone, PartialEq, Reflect)]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PropInventory {
+    /// How many props can be stashed at once.\
+    /// Default: 4
+    pub capacity: usize,
+    /// The maximum total mass in kg of all stashed props.
+    /// Uses the mass the props had before they were picked up.\
+    /// Default: 35.0 kg
+    pub max_mass: Scalar,
+    #[entities]
+    entries: Vec<PropInventoryEntry>,
+}
+
+impl Default for PropInventory {
+    fn default() -> Self {
+        Self {
+            capacity: 4,
+            max_mass: 35.0,
+            entries: Vec::new(),
         }
     }
 }
 
+impl PropInventory {
+    /// The stashed props, in the order they were stashed.
+    /// Indices into this slice are used by
+    /// [`AvianPickupAction::Retrieve`](crate::prelude::AvianPickupAction::Retrieve).
+    pub fn entries(&self) -> &[PropInventoryEntry] {
+        &self.entries
+    }
+
+    /// The number of stashed props.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no props are stashed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The total mass of all stashed props.
+    pub fn mass(&self) -> Scalar {
+        self.entries.iter().map(|entry| entry.mass).sum()
+    }
+
+    /// Whether a prop with the given mass fits into the inventory.
+    pub fn can_stash(&self, mass: Scalar) -> bool {
+        self.len() < self.capacity && self.mass() + mass <= self.max_mass
+    }
+
+    pub(crate) fn push(&mut self, entry: PropInventoryEntry) {
+        self.entries.push(entry);
+    }
+
+    pub(crate) fn remove(&mut self, index: usize) -> Option<PropInventoryEntry> {
+        (index < self.entries.len()).then(|| self.entries.remove(index))
+    }
+}
+
+/// A prop stashed in a [`PropInventory`].
+#[derive(Debug, Clone, Copy, PartialEq, MapEntities, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PropInventoryEntry {
+    /// The stashed prop.
+    #[entities]
+    pub prop: Entity,
+    /// The mass the prop had before it was picked up.
+    pub mass: Scalar,
+    /// The rotation of the prop in the actor's local space when it was
+    /// stashed. The prop is held with this rotation again when retrieved.
+    pub rotation: Quaternion,
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Component, Default, Reflect)]
 #[reflect(Debug, Component, PartialEq, Hash, Default)]
 #[cfg_attr(
@@ -261,9 +984,9 @@ pub enum AvianPickupActorState {
     /// The actor is trying to pick up an object.
     /// The object is still too far away to be picked up,
     /// so we're pulling it closer.
-    Pulling(Entity),
+    Pulling(#[entities] Entity),
     /// The actor is holding an object.
-    Holding(Entity),
+    Holding(#[entities] Entity),
 }
 
 impl AvianPickupActorState {
@@ -294,6 +1017,37 @@ impl Default for AvianPickupActor {
             pull: default(),
             hold: default(),
             throw: default(),
+            manipulation: default(),
+            place: default(),
+            attribution: default(),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_aim_ray_stays_upright_relative_to_up() {
+        let aim_ray = AimRayOverride(Ray3d::new(Vec3::ZERO, Dir3::X));
+        let transform = aim_ray.transform(Dir3::Z, Quat::IDENTITY);
+        assert!(transform.forward().dot(Vec3::X) > 0.999);
+        assert!(transform.up().dot(Vec3::Z) > 0.999);
+    }
+
+    #[test]
+    fn test_aim_ray_straight_down_faces_the_heading() {
+        let aim_ray = AimRayOverride(Ray3d::new(Vec3::Y * 10.0, Dir3::NEG_Y));
+        let heading = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        let transform = aim_ray.transform(Dir3::Y, heading);
+        assert!(transform.forward().dot(Vec3::NEG_Y) > 0.999);
+        // The top of the aim points where the actor is heading.
+        assert!(
+            transform.up().dot(Vec3::NEG_X) > 0.999,
+            "{}",
+            transform.up()
+        );
+        assert!(transform.rotation.is_finite());
+    }
+}
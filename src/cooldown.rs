@@ -2,55 +2,63 @@ use std::time::Duration;
 
 use crate::prelude::*;
 use avian3d::prelude::*;
-use bevy_platform::collections::HashMap;
 use bevy_time::prelude::*;
 
 use crate::{AvianPickupSystem, prelude::AvianPickupAction};
 
 pub(super) mod prelude {
-    pub(crate) use super::Cooldown;
+    pub use super::Cooldown;
 }
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(PhysicsSchedule, tick.in_set(AvianPickupSystem::TickTimers));
+    app.register_type::<Cooldown>()
+        .add_systems(PhysicsSchedule, tick.in_set(AvianPickupSystem::TickTimers));
 }
 
+/// Cooldowns of an [`AvianPickupActor`] that limit how often it can do
+/// things, e.g. how quickly it can pull again after throwing a prop.
+/// Inserted automatically.
+///
 /// Timings taken from [`CWeaponPhysCannon::SecondaryAttack`](https://github.com/ValveSoftware/source-sdk-2013/blob/master/src/game/server/hl2/weapon_physcannon.cpp#L2284)
-#[derive(Debug, Clone, Component)]
-pub(crate) struct Cooldown(HashMap<AvianPickupAction, Timer>);
-
-impl Default for Cooldown {
-    fn default() -> Self {
-        let map = AvianPickupAction::iter()
-            .map(|action| (action, default()))
-            .collect();
-
-        Self(map)
-    }
+#[derive(Debug, Clone, PartialEq, Component, Default, Reflect)]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Cooldown {
+    throw: Timer,
+    drop: Timer,
+    pull: Timer,
 }
 
 impl Cooldown {
-    fn get(&self, action: &AvianPickupAction) -> &Timer {
-        // Safety: all actions are always present in the map as we initialize them in `default`.
-        self.0.get(action).unwrap()
+    /// Returns `None` for actions that are not subject to a cooldown.
+    fn get(&self, action: &AvianPickupAction) -> Option<&Timer> {
+        match action {
+            AvianPickupAction::Throw => Some(&self.throw),
+            AvianPickupAction::Drop => Some(&self.drop),
+            AvianPickupAction::Pull => Some(&self.pull),
+            _ => None,
+        }
     }
 
-    fn set(&mut self, action: AvianPickupAction, seconds: f32) {
-        self.0
-            .insert(action, Timer::from_seconds(seconds, TimerMode::Once));
+    fn timers_mut(&mut self) -> impl Iterator<Item = &mut Timer> {
+        [&mut self.throw, &mut self.drop, &mut self.pull].into_iter()
     }
 
     pub(crate) fn is_finished(&self, action: AvianPickupAction) -> bool {
-        self.get(&action).is_finished()
+        self.get(&action).is_none_or(Timer::is_finished)
     }
 
     pub(crate) fn throw(&mut self) {
         // Happens to be the same as `drop`, but that's a coincidence.
-        self.set(AvianPickupAction::Pull, 0.5);
+        self.pull = Timer::from_seconds(0.5, TimerMode::Once);
     }
 
     pub(crate) fn drop(&mut self) {
-        self.set(AvianPickupAction::Pull, 0.5);
+        self.pull = Timer::from_seconds(0.5, TimerMode::Once);
     }
 
     pub(crate) fn hold(&mut self) {
@@ -58,15 +66,15 @@ impl Cooldown {
         // - [+ 0.5](https://github.com/ValveSoftware/source-sdk-2013/blob/master/src/game/server/hl2/weapon_physcannon.cpp#L2316)
         // - [+ 0.4](https://github.com/ValveSoftware/source-sdk-2013/blob/master/src/game/server/hl2/weapon_physcannon.cpp#L2438)
         // Let's use just 0.4, that feels nicer.
-        self.set(AvianPickupAction::Drop, 0.4);
+        self.drop = Timer::from_seconds(0.4, TimerMode::Once);
     }
 
     pub(crate) fn pull(&mut self) {
-        self.set(AvianPickupAction::Pull, 0.1);
+        self.pull = Timer::from_seconds(0.1, TimerMode::Once);
     }
 
     pub(crate) fn tick(&mut self, time: Duration) {
-        for timer in self.0.values_mut() {
+        for timer in self.timers_mut() {
             timer.tick(time);
         }
     }
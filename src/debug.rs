@@ -0,0 +1,479 @@
+//! Module for visualizing what Avian Pickup is doing, which helps with tuning
+//! the configuration of an [`AvianPickupActor`].
+//! Only available with the `debug` feature.
+
+use avian3d::math::{AdjustPrecision as _, AsF32 as _, Scalar, Vector};
+use bevy_color::{Color, Mix as _, palettes::css};
+use bevy_ecs::relationship::Relationship as _;
+use bevy_gizmos::prelude::*;
+
+use crate::{
+    interaction::{
+        HoldError, ShadowParams, can_pull, find_prop_in_cone, find_prop_in_trace,
+        has_line_of_sight, is_pullable,
+    },
+    math::ScalarAsF32 as _,
+    prelude::*,
+    verb::Holding,
+};
+
+pub(super) mod prelude {
+    pub use super::{AvianPickupDebug, AvianPickupDebugPlugin};
+}
+
+/// Plugin that draws gizmos for every [`AvianPickupActor`] with an
+/// [`AvianPickupDebug`]. Add this after the [`AvianPickupPlugin`].
+///
+/// The following is drawn:
+/// - The interaction cone in yellow, ending at
+///   [`AvianPickupActor::interaction_distance`].
+/// - The trace ray used to find props right in front of the actor in white,
+///   and the small box cast along it where it hits a prop.
+/// - All props in range, colored by whether they can be pulled:
+///   - green: can be pulled
+///   - blue: outside the interaction cone
+///   - purple: further away than the interaction distance
+///   - red: occluded by an obstacle
+///   - orange: too heavy
+///   - gray: already held
+///
+///   The prop the actor would pull right now is drawn with a white sphere
+///   around it.
+/// - While holding a prop: the target pose of the prop as axes, a line from
+///   the prop to its target that goes from green to red as the hold error
+///   approaches the point at which the prop is dropped, and a red cross where
+///   the prop would touch the first obstacle in front of the actor.
+///
+/// # Example
+///
+/// ```no_run
+/// # use avian3d::prelude::*;
+/// # use avian_pickup::prelude::*;
+/// # use bevy::prelude::*;
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         PhysicsPlugins::default(),
+///         AvianPickupPlugin::default(),
+///         AvianPickupDebugPlugin::default(),
+///     ))
+///     .add_systems(Startup, |mut commands: Commands| {
+///         commands.spawn((
+///             Camera3d::default(),
+///             AvianPickupActor::default(),
+///             AvianPickupDebug::default(),
+///         ));
+///     });
+/// ```
+#[derive(Default)]
+#[non_exhaustive]
+pub struct AvianPickupDebugPlugin;
+
+impl Plugin for AvianPickupDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<AvianPickupDebug>().add_systems(
+            PostUpdate,
+            (
+                draw_interaction_cone,
+                draw_trace,
+                draw_candidates,
+                draw_hold,
+            )
+                .after(bevy_transform::TransformSystems::Propagate),
+        );
+    }
+}
+
+/// Component for an [`AvianPickupActor`] that should be visualized by the
+/// [`AvianPickupDebugPlugin`]. Each part of the visualization can be toggled
+/// separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component, Reflect)]
+#[reflect(Debug, Component, Default, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[require(HeldPropObstacleHit)]
+pub struct AvianPickupDebug {
+    /// Whether anything is drawn for this actor at all.\
+    /// Default: `true`
+    pub enabled: bool,
+    /// Whether to draw the interaction cone.\
+    /// Default: `true`
+    pub interaction_cone: bool,
+    /// Whether to draw the trace ray and box cast.\
+    /// Default: `true`
+    pub trace: bool,
+    /// Whether to draw the props in range and why they can or cannot be
+    /// pulled.\
+    /// Default: `true`
+    pub candidates: bool,
+    /// Whether to draw the target pose, hold error and obstacle hit of a held
+    /// prop.\
+    /// Default: `true`
+    pub hold: bool,
+}
+
+impl Default for AvianPickupDebug {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interaction_cone: true,
+            trace: true,
+            candidates: true,
+            hold: true,
+        }
+    }
+}
+
+/// Where the center of mass of the prop held by an actor with an
+/// [`AvianPickupDebug`] would be when it touches the first obstacle in front
+/// of the actor, if there is one.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Default)]
+pub(crate) struct HeldPropObstacleHit(pub(crate) Option<Vector>);
+
+/// Why a prop in range of an actor cannot be pulled right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rejection {
+    OutsideCone,
+    OutOfRange,
+    Occluded,
+    TooHeavy,
+    AlreadyHeld,
+}
+
+impl Rejection {
+    fn color(rejection: Option<Self>) -> Color {
+        match rejection {
+            None => css::LIME,
+            Some(Self::OutsideCone) => css::DODGER_BLUE,
+            Some(Self::OutOfRange) => css::MEDIUM_PURPLE,
+            Some(Self::Occluded) => css::RED,
+            Some(Self::TooHeavy) => css::ORANGE,
+            Some(Self::AlreadyHeld) => css::GRAY,
+        }
+        .into()
+    }
+}
+
+fn draw_interaction_cone(
+    mut gizmos: Gizmos,
+    q_actor: Query<(
+        &GlobalTransform,
+        &AvianPickupActor,
+        &AvianPickupDebug,
+        Option<&PickupAimOrigin>,
+        Option<&AimRayOverride>,
+        Option<&PickupUpDirection>,
+    )>,
+    q_transform: Query<&GlobalTransform>,
+    length_unit: Res<PhysicsLengthUnit>,
+    gravity: Res<Gravity>,
+) {
+    for (transform, config, debug, aim_origin, aim_ray, up_direction) in q_actor.iter() {
+        if !(debug.enabled && debug.interaction_cone) {
+            continue;
+        }
+        let up = PickupUpDirection::resolve(up_direction, config, &gravity);
+        let transform = PickupAimOrigin::resolve(aim_origin, aim_ray, transform, up, &q_transform);
+        // Gizmos are drawn in single precision.
+        let half_angle = config.interaction_cone.clamp(-1.0, 1.0).acos().f32();
+        let distance = (config.interaction_distance * length_unit.0).f32();
+        let center = transform.translation + transform.forward() * distance * half_angle.cos();
+        let radius = distance * half_angle.sin();
+        let color = css::YELLOW;
+        // The circle lies in the local XY plane, i.e. facing the actor.
+        gizmos.circle(Isometry3d::new(center, transform.rotation), radius, color);
+        for direction in [
+            transform.right(),
+            transform.left(),
+            transform.up(),
+            transform.down(),
+        ] {
+            gizmos.line(transform.translation, center + direction * radius, color);
+        }
+    }
+}
+
+fn draw_trace(
+    mut gizmos: Gizmos,
+    spatial_query: SpatialQuery,
+    q_actor: Query<(
+        &GlobalTransform,
+        &AvianPickupActor,
+        &AvianPickupDebug,
+        Option<&PickupAimOrigin>,
+        Option<&AimRayOverride>,
+        Option<&PickupUpDirection>,
+    )>,
+    q_transform: Query<&GlobalTransform>,
+    q_rigid_body: Query<&RigidBody>,
+    q_socketed: Query<&SocketedProp>,
+    q_socket: Query<&PropSocket>,
+    q_collider_parent: Query<&ColliderOf>,
+    length_unit: Res<PhysicsLengthUnit>,
+    gravity: Res<Gravity>,
+) {
+    let length_unit = length_unit.0;
+    for (transform, config, debug, aim_origin, aim_ray, up_direction) in q_actor.iter() {
+        if !(debug.enabled && debug.trace) {
+            continue;
+        }
+        let up = PickupUpDirection::resolve(up_direction, config, &gravity);
+        let transform = PickupAimOrigin::resolve(aim_origin, aim_ray, transform, up, &q_transform);
+        gizmos.ray(
+            transform.translation,
+            transform.forward() * (config.interaction_distance * length_unit).f32(),
+            css::WHITE,
+        );
+        let is_prop = |entity: Entity| is_pullable(entity, &q_rigid_body, &q_socketed, &q_socket);
+        let Some(prop) = find_prop_in_trace(
+            &spatial_query,
+            transform,
+            config,
+            length_unit,
+            is_prop,
+            &q_collider_parent,
+        ) else {
+            continue;
+        };
+        // Same size as the box used for the cast.
+        let hit = transform.translation + transform.forward() * prop.toi.f32();
+        gizmos.cube(
+            Transform::from_translation(hit)
+                .with_rotation(transform.rotation)
+                .with_scale(Vec3::splat((0.02 * length_unit).f32())),
+            css::WHITE,
+        );
+    }
+}
+
+fn draw_candidates(
+    mut gizmos: Gizmos,
+    spatial_query: SpatialQuery,
+    q_actor: Query<(
+        Entity,
+        &GlobalTransform,
+        &AvianPickupActor,
+        &AvianPickupDebug,
+        Option<&PickupAimOrigin>,
+        Option<&AimRayOverride>,
+        Option<&PickupHoldAnchor>,
+        Option<&PickupUpDirection>,
+    )>,
+    q_rigid_body: Query<(&RigidBody, &ComputedMass, &GlobalTransform, Has<HeldProp>)>,
+    q_rigid_body_type: Query<&RigidBody>,
+    q_socketed: Query<&SocketedProp>,
+    q_socket: Query<&PropSocket>,
+    q_position: Query<&GlobalTransform>,
+    q_collider_parent: Query<&ColliderOf>,
+    q_layers: Query<&CollisionLayers>,
+    q_parent: Query<&ChildOf>,
+    q_rigid_body_colliders: Query<&RigidBodyColliders>,
+    q_collider: Query<(&Position, &Rotation, &Collider)>,
+    length_unit: Res<PhysicsLengthUnit>,
+    gravity: Res<Gravity>,
+) {
+    let length_unit = length_unit.0;
+    for (actor, actor_transform, config, debug, aim_origin, aim_ray, hold_anchor, up_direction) in
+        q_actor.iter()
+    {
+        if !(debug.enabled && debug.candidates) {
+            continue;
+        }
+        let up = PickupUpDirection::resolve(up_direction, config, &gravity);
+        let transform =
+            PickupAimOrigin::resolve(aim_origin, aim_ray, actor_transform, up, &q_position);
+        let hold_origin =
+            PickupHoldAnchor::resolve(hold_anchor, transform, actor_transform, &q_position);
+        let is_prop =
+            |entity: Entity| is_pullable(entity, &q_rigid_body_type, &q_socketed, &q_socket);
+
+        // Slightly larger than the interaction distance so that props just
+        // out of range are shown as well.
+        let search_distance = config.interaction_distance * length_unit * 1.5;
+        let search_aabb = ColliderAabb::new(
+            transform.translation.adjust_precision(),
+            Vector::splat(search_distance),
+        );
+        let mut props = spatial_query
+            .aabb_intersections_with_aabb(search_aabb)
+            .into_iter()
+            .filter(|&collider| {
+                let layers = q_layers.get(collider).copied().unwrap_or_default();
+                config.prop_filter.test(collider, layers)
+            })
+            .filter_map(|entity| q_collider_parent.get(entity).ok())
+            .map(|collider_parent| collider_parent.get())
+            .filter(|entity| is_prop(*entity))
+            .collect::<Vec<_>>();
+        props.sort();
+        props.dedup();
+
+        for prop in props {
+            let Ok((_, &mass, prop_transform, is_held)) = q_rigid_body.get(prop) else {
+                continue;
+            };
+            let position = prop_transform.translation();
+            let rejection = rejection(
+                &spatial_query,
+                transform,
+                hold_origin.translation,
+                actor,
+                config,
+                length_unit,
+                prop,
+                position,
+                mass,
+                is_held,
+                &q_collider_parent,
+                &q_layers,
+                &q_parent,
+                &q_rigid_body_colliders,
+                &q_collider,
+            );
+            gizmos.sphere(
+                Isometry3d::from_translation(position),
+                (0.1 * length_unit).f32(),
+                Rejection::color(rejection),
+            );
+        }
+
+        let target = find_prop_in_trace(
+            &spatial_query,
+            transform,
+            config,
+            length_unit,
+            is_prop,
+            &q_collider_parent,
+        )
+        .or_else(|| {
+            find_prop_in_cone(
+                &spatial_query,
+                transform,
+                config,
+                length_unit,
+                &q_position,
+                is_prop,
+                &q_collider_parent,
+                &q_layers,
+            )
+        });
+        if let Some(target_transform) = target.and_then(|target| q_position.get(target.entity).ok())
+        {
+            gizmos.sphere(
+                Isometry3d::from_translation(target_transform.translation()),
+                (0.2 * length_unit).f32(),
+                css::WHITE,
+            );
+        }
+    }
+}
+
+/// Mirrors the checks done when pulling a prop through the interaction cone.
+fn rejection(
+    spatial_query: &SpatialQuery,
+    origin: Transform,
+    hold_origin: Vec3,
+    actor: Entity,
+    config: &AvianPickupActor,
+    length_unit: Scalar,
+    prop: Entity,
+    position: Vec3,
+    mass: ComputedMass,
+    is_held: bool,
+    q_collider_parent: &Query<&ColliderOf>,
+    q_layers: &Query<&CollisionLayers>,
+    q_parent: &Query<&ChildOf>,
+    q_rigid_body_colliders: &Query<&RigidBodyColliders>,
+    q_collider: &Query<(&Position, &Rotation, &Collider)>,
+) -> Option<Rejection> {
+    if is_held {
+        return Some(Rejection::AlreadyHeld);
+    }
+    let Ok((los, distance)) = Dir3::new_and_length(position - origin.translation) else {
+        return None;
+    };
+    let distance = distance.adjust_precision();
+    if distance >= config.interaction_distance * length_unit {
+        return Some(Rejection::OutOfRange);
+    }
+    if los.dot(origin.forward().into()).adjust_precision() <= config.interaction_cone {
+        return Some(Rejection::OutsideCone);
+    }
+    if let Some(hit) = spatial_query.cast_ray(
+        origin.translation.adjust_precision(),
+        los,
+        distance,
+        true,
+        &config.obstacle_filter,
+    ) {
+        let hit_rigid_body = q_collider_parent
+            .get(hit.entity)
+            .map_or(hit.entity, |collider_parent| collider_parent.get());
+        if hit_rigid_body != prop {
+            return Some(Rejection::Occluded);
+        }
+    }
+    if hold_origin != origin.translation
+        && !has_line_of_sight(
+            spatial_query,
+            hold_origin.adjust_precision(),
+            actor,
+            prop,
+            position.adjust_precision(),
+            config,
+            q_collider_parent,
+            q_layers,
+            q_parent,
+            q_rigid_body_colliders,
+            q_collider,
+        )
+    {
+        return Some(Rejection::Occluded);
+    }
+    if !can_pull(mass, config) {
+        return Some(Rejection::TooHeavy);
+    }
+    None
+}
+
+fn draw_hold(
+    mut gizmos: Gizmos,
+    q_actor: Query<(
+        &AvianPickupDebug,
+        &ShadowParams,
+        &HoldError,
+        &HeldPropObstacleHit,
+        &Holding,
+    )>,
+    q_prop: Query<&GlobalTransform>,
+    length_unit: Res<PhysicsLengthUnit>,
+) {
+    let length_unit = length_unit.0;
+    for (debug, shadow, hold_error, obstacle_hit, holding) in q_actor.iter() {
+        if !(debug.enabled && debug.hold) {
+            continue;
+        }
+        let target_position = shadow.target_position.f32();
+        let target = Transform::from_translation(target_position)
+            .with_rotation(shadow.target_rotation.f32());
+        gizmos.axes(target, (0.3 * length_unit).f32());
+        if let Ok(prop_transform) = q_prop.get(holding.0) {
+            let error = (hold_error.error / (HoldError::MAX * length_unit))
+                .clamp(0.0, 1.0)
+                .f32();
+            let color = Color::from(css::LIME).mix(&Color::from(css::RED), error);
+            gizmos.line(prop_transform.translation(), target_position, color);
+        }
+        if let Some(hit) = obstacle_hit.0 {
+            gizmos.cross(
+                Isometry3d::from_translation(hit.f32()),
+                (0.1 * length_unit).f32(),
+                css::RED,
+            );
+        }
+    }
+}
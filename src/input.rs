@@ -1,10 +1,18 @@
 //! Module for the types that represent input events for Avian Pickup.
 
+use std::{
+    hash::{Hash, Hasher},
+    mem::Discriminant,
+};
+
+use avian3d::math::Scalar;
 use bevy_platform::collections::HashSet;
 
 use crate::{
-    interaction::{HoldError, ShadowParams},
+    interaction::{HoldError, HoldManipulation, ShadowParams},
     prelude::*,
+    prop::PrePickupMass,
+    state::AvianPickupTick,
     verb::{SetVerb, Verb},
 };
 
@@ -14,11 +22,24 @@ pub(super) mod prelude {
 
 pub(super) fn plugin(app: &mut App) {
     app.add_message::<AvianPickupInput>()
-        .add_systems(PostUpdate, set_verbs_according_to_input);
+        .init_resource::<AvianPickupInputBuffer>()
+        .add_systems(PreUpdate, buffer_inputs)
+        .add_systems(
+            PhysicsSchedule,
+            set_verbs_according_to_input.in_set(AvianPickupSystem::First),
+        );
 }
 
 /// Message for picking up and throwing objects.
 /// Send this to tell Avian Pickup to do its thing.
+///
+/// Inputs are active in every physics update that runs in the frame after
+/// they were sent, like a button that is held down for the whole frame.
+/// If no physics update runs in that frame, they stay active until one does.
+/// Manipulating the held prop, e.g. through [`AvianPickupAction::Rotate`], is
+/// only applied once, in the first of these physics updates.\
+/// If [`AvianPickupInput::tick`] is set, the input is instead consumed in the
+/// physics update with that [`AvianPickupTick`](crate::state::AvianPickupTick).
 #[derive(Message, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 #[reflect(Debug, PartialEq)]
 #[cfg_attr(
@@ -31,10 +52,47 @@ pub struct AvianPickupInput {
     pub actor: Entity,
     /// The kind of input that the event represents.
     pub action: AvianPickupAction,
+    /// The [`AvianPickupTick`](crate::state::AvianPickupTick) of the physics
+    /// update this input belongs to. Inputs for future ticks are held back
+    /// until then, inputs for past ticks are consumed right away.
+    /// `None` means that the input is active in the physics updates of the next
+    /// frame.\
+    /// Useful for networking, where inputs are often received ahead of time.
+    pub tick: Option<u64>,
+}
+
+impl AvianPickupInput {
+    /// Creates an input that is active in the physics updates of the next frame.
+    pub fn new(actor: Entity, action: AvianPickupAction) -> Self {
+        Self {
+            actor,
+            action,
+            tick: None,
+        }
+    }
+
+    /// Consume this input in the physics update with the given
+    /// [`AvianPickupTick`](crate::state::AvianPickupTick) instead.
+    pub fn with_tick(mut self, tick: u64) -> Self {
+        self.tick = Some(tick);
+        self
+    }
 }
 
-/// The kind of input that the [`AvianPickupInput`] represents.
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Reflect)]
+/// Inputs that were received, but not consumed yet.
+#[derive(Resource, Debug, Clone, PartialEq, Default)]
+pub(crate) struct AvianPickupInputBuffer {
+    /// Inputs with an [`AvianPickupInput::tick`] that was not reached yet.
+    pub(crate) scheduled: Vec<AvianPickupInput>,
+    /// Inputs without a tick, active in every physics update of the frame.
+    pub(crate) frame: Vec<AvianPickupInput>,
+    /// Whether a physics update already ran with the inputs in [`Self::frame`].
+    pub(crate) frame_consumed: bool,
+}
+
+/// The kind of input that the [`AvianPickupInput`] represents.\
+/// Two actions are equal if their payloads are bitwise equal.
+#[derive(Debug, Clone, Copy, Reflect)]
 #[reflect(Debug, PartialEq, Hash)]
 #[cfg_attr(
     feature = "serialize",
@@ -48,16 +106,146 @@ pub enum AvianPickupAction {
     Drop,
     /// The right mouse button was pressed.
     Pull,
+    /// Rotate the held prop by the given angles in radians.
+    /// The angles are scaled by
+    /// [`AvianPickupActorManipulationConfig::rotation_sensitivity`](crate::prelude::AvianPickupActorManipulationConfig::rotation_sensitivity)
+    /// and applied in the space configured by
+    /// [`AvianPickupActorManipulationConfig::rotation_space`](crate::prelude::AvianPickupActorManipulationConfig::rotation_space).\
+    /// Multiple rotations sent before the next physics update are accumulated.
+    /// Ignored if the actor is not holding a prop.
+    Rotate {
+        /// Rotation around the up axis.
+        yaw: Scalar,
+        /// Rotation around the actor's right axis.
+        pitch: Scalar,
+        /// Rotation around the actor's forward axis.
+        roll: Scalar,
+    },
+    /// Move the held prop closer to the actor (negative values) or further
+    /// away from it (positive values).
+    /// The value is scaled by
+    /// [`AvianPickupActorManipulationConfig::distance_sensitivity`](crate::prelude::AvianPickupActorManipulationConfig::distance_sensitivity)
+    /// and the resulting distance is clamped to
+    /// [`AvianPickupActorManipulationConfig::distance_range`](crate::prelude::AvianPickupActorManipulationConfig::distance_range).\
+    /// Multiple adjustments sent before the next physics update are accumulated.
+    /// Ignored if the actor is not holding a prop.
+    AdjustDistance(Scalar),
+    /// Snap the held prop's rotation to the nearest rotation aligned with the
+    /// coordinate axes of the actor's heading, no matter how far away it is.
+    /// The prop keeps this rotation relative to the actor afterwards.\
+    /// Uses the increment of
+    /// [`AvianPickupActorHoldConfig::rotation_snapping`](crate::prelude::AvianPickupActorHoldConfig::rotation_snapping)
+    /// if set, or 90 degrees otherwise.
+    /// Ignored if the actor is not holding a prop.
+    SnapRotation,
+    /// Release the held prop and freeze it exactly where it is by turning it
+    /// into a [`RigidBody::Static`] marked with a
+    /// [`FrozenProp`](crate::prelude::FrozenProp).
+    Freeze,
+    /// Unfreeze the frozen prop the actor is looking at, turning it back into
+    /// a [`RigidBody::Dynamic`].
+    Unfreeze,
+    /// Place the held prop on the surface the actor is looking at and release
+    /// it without any velocity.
+    /// See [`AvianPickupActorPlaceConfig`](crate::prelude::AvianPickupActorPlaceConfig)
+    /// for configuration.\
+    /// If the prop cannot be placed there, the actor keeps holding it and a
+    /// [`PropPlacementFailed`](crate::prelude::PropPlacementFailed) is sent.
+    /// Ignored if the actor is not holding a prop.
+    Place,
+    /// Stash the held prop into the actor's
+    /// [`PropInventory`](crate::prelude::PropInventory), taking it out of the
+    /// physics world until it is retrieved again.\
+    /// Ignored if the actor is not holding a prop, has no inventory, or the
+    /// inventory has no room for the prop.
+    Stash,
+    /// Retrieve the prop at the given index of the actor's
+    /// [`PropInventory`](crate::prelude::PropInventory) and start holding it
+    /// right away.\
+    /// Ignored if the actor is already holding a prop or there is no prop at
+    /// the given index.
+    Retrieve(usize),
+    /// Launch the prop the actor is looking at without picking it up, like the
+    /// physcannon does when not holding anything.\
+    /// Props are punted with the same speed they would be thrown with.
+    /// Ignored if the actor is holding a prop.
+    Punt,
 }
 
 impl AvianPickupAction {
-    pub(crate) fn iter() -> impl Iterator<Item = Self> {
-        [Self::Throw, Self::Drop, Self::Pull].iter().copied()
+    /// Returns `true` for actions that are handled while the prop is being held
+    /// instead of directly changing what the actor is doing.
+    pub(crate) fn is_manipulation(self) -> bool {
+        matches!(
+            self,
+            AvianPickupAction::Rotate { .. }
+                | AvianPickupAction::AdjustDistance(..)
+                | AvianPickupAction::SnapRotation
+                | AvianPickupAction::Place
+        )
+    }
+
+    /// The action with its payload in a form that can be compared and hashed.
+    fn key(self) -> (Discriminant<Self>, [u64; 3]) {
+        // Works for both `f32` and `f64` scalars without casting.
+        let bits = |value: Scalar| {
+            let mut bytes = [0; 8];
+            let value_bytes = value.to_le_bytes();
+            bytes[..value_bytes.len()].copy_from_slice(&value_bytes);
+            u64::from_le_bytes(bytes)
+        };
+        let payload = match self {
+            Self::Rotate { yaw, pitch, roll } => [bits(yaw), bits(pitch), bits(roll)],
+            Self::AdjustDistance(distance) => [bits(distance), 0, 0],
+            Self::Retrieve(index) => [index as u64, 0, 0],
+            Self::Throw
+            | Self::Drop
+            | Self::Pull
+            | Self::SnapRotation
+            | Self::Freeze
+            | Self::Unfreeze
+            | Self::Place
+            | Self::Stash
+            | Self::Punt => [0; 3],
+        };
+        (std::mem::discriminant(&self), payload)
     }
 }
 
-fn set_verbs_according_to_input(
+impl PartialEq for AvianPickupAction {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for AvianPickupAction {}
+
+impl Hash for AvianPickupAction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+fn buffer_inputs(
     mut r_input: MessageReader<AvianPickupInput>,
+    mut buffer: ResMut<AvianPickupInputBuffer>,
+) {
+    // Inputs of frames without a physics update carry over to the next one.
+    if buffer.frame_consumed {
+        buffer.frame.clear();
+        buffer.frame_consumed = false;
+    }
+    for &input in r_input.read() {
+        match input.tick {
+            Some(_) => buffer.scheduled.push(input),
+            None => buffer.frame.push(input),
+        }
+    }
+}
+
+fn set_verbs_according_to_input(
+    tick: Res<AvianPickupTick>,
+    mut buffer: ResMut<AvianPickupInputBuffer>,
     mut commands: Commands,
     q_actor: Query<
         (
@@ -67,16 +255,31 @@ fn set_verbs_according_to_input(
             Has<GlobalTransform>,
             Has<ShadowParams>,
             Has<HoldError>,
+            Option<&PropInventory>,
         ),
         With<AvianPickupActor>,
     >,
+    mut q_manipulation: Query<&mut HoldManipulation>,
+    q_prop_mass: Query<&PrePickupMass>,
 ) {
     let mut unhandled_actors: HashSet<_> = q_actor.iter().map(|(entity, ..)| entity).collect();
-    'outer: for &event in r_input.read() {
+    let (due_inputs, pending_inputs): (Vec<_>, Vec<_>) = std::mem::take(&mut buffer.scheduled)
+        .into_iter()
+        .partition(|input| input.tick.is_some_and(|input_tick| input_tick <= tick.0));
+    buffer.scheduled = pending_inputs;
+    let frame_inputs = buffer.frame.clone();
+    // The prop is only manipulated once per frame, the verbs stay active.
+    buffer.frame.retain(|input| !input.action.is_manipulation());
+    buffer.frame_consumed = true;
+    'outer: for event in due_inputs.into_iter().chain(frame_inputs) {
         let action = event.action;
         let actor = event.actor;
-        unhandled_actors.remove(&actor);
-        let Ok((_entity, state, cooldown, has_global_transform, has_shadow, has_error)) =
+        // Manipulating the held prop does not count as an input that keeps the
+        // actor doing what it is doing, e.g. pulling.
+        if !action.is_manipulation() {
+            unhandled_actors.remove(&actor);
+        }
+        let Ok((_entity, state, cooldown, has_global_transform, has_shadow, has_error, inventory)) =
             q_actor.get(actor)
         else {
             error!(
@@ -112,6 +315,15 @@ fn set_verbs_according_to_input(
             continue;
         };
 
+        if action.is_manipulation() {
+            if state.is_holding()
+                && let Ok(mut manipulation) = q_manipulation.get_mut(actor)
+            {
+                manipulation.accumulate(action);
+            }
+            continue;
+        }
+
         let verb = match action {
             AvianPickupAction::Throw
                 if cooldown.is_finished(AvianPickupAction::Throw)
@@ -134,6 +346,51 @@ fn set_verbs_according_to_input(
                     forced: false,
                 })
             }
+            AvianPickupAction::Freeze
+                if matches!(state, AvianPickupActorState::Holding(..))
+                    && cooldown.is_finished(AvianPickupAction::Drop) =>
+            {
+                let AvianPickupActorState::Holding(prop) = state else {
+                    unreachable!()
+                };
+                Some(Verb::Freeze(prop))
+            }
+            AvianPickupAction::Stash
+                if matches!(state, AvianPickupActorState::Holding(..))
+                    && cooldown.is_finished(AvianPickupAction::Drop) =>
+            {
+                let AvianPickupActorState::Holding(prop) = state else {
+                    unreachable!()
+                };
+                let mass = q_prop_mass.get(prop).map_or(0.0, |mass| mass.0);
+                inventory
+                    .is_some_and(|inventory| inventory.can_stash(mass))
+                    .then_some(Verb::Stash(prop))
+            }
+            AvianPickupAction::Retrieve(index)
+                if matches!(
+                    state,
+                    AvianPickupActorState::Idle | AvianPickupActorState::Pulling(..)
+                ) && inventory.is_some_and(|inventory| index < inventory.len()) =>
+            {
+                Some(Verb::Retrieve(index))
+            }
+            AvianPickupAction::Punt
+                if matches!(
+                    state,
+                    AvianPickupActorState::Idle | AvianPickupActorState::Pulling(..)
+                ) && cooldown.is_finished(AvianPickupAction::Throw) =>
+            {
+                Some(Verb::Punt)
+            }
+            AvianPickupAction::Unfreeze
+                if matches!(
+                    state,
+                    AvianPickupActorState::Idle | AvianPickupActorState::Pulling(..)
+                ) =>
+            {
+                Some(Verb::Unfreeze)
+            }
             AvianPickupAction::Pull
                 if matches!(
                     state,
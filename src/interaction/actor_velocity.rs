@@ -0,0 +1,50 @@
+use avian3d::math::{AdjustPrecision as _, Quaternion, Vector};
+use bevy_time::Time;
+
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ActorVelocity>().add_systems(
+        PhysicsSchedule,
+        estimate_actor_velocity.in_set(AvianPickupSystem::First),
+    );
+}
+
+/// Velocity of an [`AvianPickupActor`] estimated from how its transform
+/// changed between physics updates. Used for actors that are not part of a
+/// rigid body. Inserted automatically.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Default, Reflect)]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ActorVelocity {
+    pub(crate) translation: Option<Vector>,
+    pub(crate) rotation: Quaternion,
+    pub(crate) linear: Vector,
+    pub(crate) angular: Vector,
+}
+
+fn estimate_actor_velocity(
+    time: Res<Time>,
+    mut q_actor: Query<(&GlobalTransform, &mut ActorVelocity)>,
+) {
+    let dt = time.delta_secs_f64().adjust_precision();
+    for (transform, mut velocity) in q_actor.iter_mut() {
+        let (_, rotation, translation) = transform.to_scale_rotation_translation();
+        let (rotation, translation) = (rotation.adjust_precision(), translation.adjust_precision());
+        if let Some(previous_translation) = velocity.translation
+            && dt > 0.0
+        {
+            velocity.linear = (translation - previous_translation) / dt;
+            let delta = rotation * velocity.rotation.inverse();
+            // Take the short way around
+            let delta = if delta.w < 0.0 { -delta } else { delta };
+            velocity.angular = delta.to_scaled_axis() / dt;
+        }
+        velocity.translation = Some(translation);
+        velocity.rotation = rotation;
+    }
+}
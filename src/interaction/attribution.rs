@@ -0,0 +1,35 @@
+use bevy_time::Time;
+
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PhysicsSchedule,
+        expire_last_handled_by.in_set(AvianPickupSystem::TickTimers),
+    );
+}
+
+/// Removes [`LastHandledBy`] from props once it is no longer meaningful.
+/// Uses the configuration of the actor that released the prop, or the default
+/// configuration if that actor no longer exists.
+fn expire_last_handled_by(
+    mut commands: Commands,
+    time: Res<Time>,
+    q_prop: Query<(Entity, &LastHandledBy, Has<Sleeping>)>,
+    q_actor: Query<&AvianPickupActor>,
+) {
+    let now = time.elapsed_secs();
+    let default_config = AvianPickupActorAttributionConfig::default();
+    for (prop, last_handled_by, is_sleeping) in q_prop.iter() {
+        let config = q_actor
+            .get(last_handled_by.actor)
+            .map_or(&default_config, |actor| &actor.attribution);
+        let timed_out = config
+            .timeout
+            .is_some_and(|timeout| now - last_handled_by.at > timeout);
+        let came_to_rest = config.clear_on_rest && is_sleeping;
+        if timed_out || came_to_rest {
+            commands.entity(prop).remove::<LastHandledBy>();
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use avian3d::math::{Scalar, TAU};
+use bevy_time::Time;
 
 use crate::{prelude::*, verb::Dropping};
 
@@ -12,12 +13,19 @@ fn drop(
     mut q_actor: Query<(Entity, &mut AvianPickupActorState, &mut Cooldown, &Dropping)>,
     mut q_prop: Query<(&mut LinearVelocity, &mut AngularVelocity)>,
     mut w_drop_event: MessageWriter<PropDropped>,
+    time: Res<Time>,
+    length_unit: Res<PhysicsLengthUnit>,
 ) {
     for (actor, mut state, mut cooldown, drop) in q_actor.iter_mut() {
         let prop = drop.prop;
         *state = AvianPickupActorState::Idle;
         cooldown.drop();
         commands.entity(actor).remove::<Dropping>();
+        commands.entity(prop).try_insert(LastHandledBy {
+            actor,
+            action: HandlingAction::Dropped,
+            at: time.elapsed_secs(),
+        });
         w_drop_event.write(PropDropped {
             actor,
             prop,
@@ -34,7 +42,7 @@ fn drop(
         const HL2_NORM_SPEED: Scalar = 5.0;
         const MAX_DROP_LINEAR_SPEED: Scalar = HL2_NORM_SPEED * 1.5;
         const MAX_DROP_ANGULAR_SPEED: Scalar = TAU * 2.0;
-        velocity.0 = velocity.clamp_length_max(MAX_DROP_LINEAR_SPEED);
+        velocity.0 = velocity.clamp_length_max(MAX_DROP_LINEAR_SPEED * length_unit.0);
         angvel.0 = angvel.clamp_length_max(MAX_DROP_ANGULAR_SPEED);
     }
 }
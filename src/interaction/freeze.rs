@@ -0,0 +1,99 @@
+use avian3d::math::Vector;
+use bevy_time::Time;
+
+use super::pull::find_prop_in_trace;
+use crate::{
+    prelude::*,
+    verb::{Freezing, Unfreezing},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(PhysicsSchedule, freeze.in_set(HandleVerbSystem::Freeze))
+        .add_systems(PhysicsSchedule, unfreeze.in_set(HandleVerbSystem::Unfreeze));
+}
+
+/// Releases the held prop and leaves it hanging in the air, like Garry's Mod's
+/// physics gun does.
+/// By the time this runs, the prop's original mass has already been restored
+/// by removing [`Holding`](crate::verb::Holding), so unfreezing it later
+/// behaves just like dropping it would have.
+fn freeze(
+    mut commands: Commands,
+    mut q_actor: Query<(Entity, &mut AvianPickupActorState, &mut Cooldown, &Freezing)>,
+    mut q_prop: Query<(&mut LinearVelocity, &mut AngularVelocity)>,
+    mut w_freeze_event: MessageWriter<PropFrozen>,
+    time: Res<Time>,
+) {
+    for (actor, mut state, mut cooldown, freezing) in q_actor.iter_mut() {
+        let prop = freezing.0;
+        *state = AvianPickupActorState::Idle;
+        cooldown.drop();
+        commands.entity(actor).remove::<Freezing>();
+        commands.entity(prop).try_insert(LastHandledBy {
+            actor,
+            action: HandlingAction::Frozen,
+            at: time.elapsed_secs(),
+        });
+        let Ok((mut velocity, mut angvel)) = q_prop.get_mut(prop) else {
+            error!("Prop entity was deleted or in an invalid state. Ignoring.");
+            continue;
+        };
+        velocity.0 = Vector::ZERO;
+        angvel.0 = Vector::ZERO;
+        commands
+            .entity(prop)
+            .try_insert((RigidBody::Static, FrozenProp { by: actor }));
+        w_freeze_event.write(PropFrozen { actor, prop });
+    }
+}
+
+fn unfreeze(
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    mut q_actor: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &AvianPickupActor,
+            &mut Cooldown,
+            Option<&PickupAimOrigin>,
+            Option<&AimRayOverride>,
+            Option<&PickupUpDirection>,
+        ),
+        With<Unfreezing>,
+    >,
+    q_transform: Query<&GlobalTransform>,
+    q_collider_parent: Query<&ColliderOf>,
+    q_frozen: Query<(), With<FrozenProp>>,
+    length_unit: Res<PhysicsLengthUnit>,
+    gravity: Res<Gravity>,
+    mut w_unfreeze_event: MessageWriter<PropUnfrozen>,
+) {
+    for (actor, actor_transform, config, mut cooldown, aim_origin, aim_ray, up_direction) in
+        q_actor.iter_mut()
+    {
+        commands.entity(actor).remove::<Unfreezing>();
+        let up = PickupUpDirection::resolve(up_direction, config, &gravity);
+        let prop = find_prop_in_trace(
+            &spatial_query,
+            PickupAimOrigin::resolve(aim_origin, aim_ray, actor_transform, up, &q_transform),
+            config,
+            length_unit.0,
+            |entity| q_frozen.contains(entity),
+            &q_collider_parent,
+        );
+        let Some(prop) = prop else {
+            continue;
+        };
+        commands
+            .entity(prop.entity)
+            .try_insert(RigidBody::Dynamic)
+            .remove::<FrozenProp>();
+        // Don't immediately pick up the prop we just unfroze.
+        cooldown.pull();
+        w_unfreeze_event.write(PropUnfrozen {
+            actor,
+            prop: prop.entity,
+        });
+    }
+}
@@ -1,14 +1,26 @@
 use crate::prelude::*;
-use avian3d::math::{Scalar, TAU};
+use avian3d::math::{Quaternion, Scalar, TAU, Vector};
 
-pub(super) fn plugin(_app: &mut App) {}
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ShadowParams>()
+        .register_type::<HoldError>()
+        .register_type::<HoldManipulation>();
+}
 
-#[derive(Debug, Copy, Clone, Component)]
-pub(crate) struct ShadowParams {
+/// Where the prop held by an [`AvianPickupActor`] is moved to and how fast.
+/// Inserted automatically.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ShadowParams {
     /// Global target position of the held prop
-    pub(crate) target_position: Vec3,
+    pub(crate) target_position: Vector,
     /// Global target rotation of the held prop
-    pub(crate) target_rotation: Quat,
+    pub(crate) target_rotation: Quaternion,
     pub(crate) max_angular: Scalar,
     pub(crate) max_speed: Scalar,
 }
@@ -16,8 +28,8 @@ pub(crate) struct ShadowParams {
 impl Default for ShadowParams {
     fn default() -> Self {
         Self {
-            target_position: Vec3::ZERO,
-            target_rotation: Quat::IDENTITY,
+            target_position: Vector::ZERO,
+            target_rotation: Quaternion::IDENTITY,
             // the following two are tuned by hand
             max_angular: TAU * 2.0,
             max_speed: 10.0,
@@ -27,20 +39,97 @@ impl Default for ShadowParams {
 
 /// Cache for accumulating errors when holding an object.
 /// When this reaches a critical value, the object will be dropped.
-#[derive(Debug, Copy, Clone, Component)]
-pub(crate) struct HoldError {
+/// Inserted automatically on an [`AvianPickupActor`].
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct HoldError {
     /// Time until error starts accumulating
-    pub(crate) error_time: f32,
+    pub(crate) error_time: Scalar,
     /// The distance between the object and the target position
-    pub(crate) error: f32,
+    pub(crate) error: Scalar,
 }
 
 impl HoldError {
+    /// When the error exceeds this, the prop is dropped.
+    /// 12 inches in the source engine.
+    pub(crate) const MAX: Scalar = 0.3048;
+
     pub(crate) fn reset(&mut self) {
         *self = Self::default();
     }
 }
 
+/// Manipulation of the held prop requested through
+/// [`AvianPickupAction::Rotate`] and [`AvianPickupAction::AdjustDistance`].
+/// Inserted automatically on an [`AvianPickupActor`].
+#[derive(Debug, Copy, Clone, PartialEq, Component, Default, Reflect)]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct HoldManipulation {
+    /// Yaw, pitch and roll that were requested but not applied yet
+    pub(crate) pending_rotation: Vector,
+    /// Distance change that was requested but not applied yet
+    pub(crate) pending_distance: Scalar,
+    /// Whether snapping the rotation was requested but not applied yet
+    pub(crate) pending_snap: bool,
+    /// Whether placing the prop was requested but not attempted yet
+    pub(crate) pending_place: bool,
+    /// Rotation in actor space that is applied on top of the prop's own
+    /// actor space rotation
+    pub(crate) rotation: Quaternion,
+    /// Offset to the preferred distance of the held prop
+    pub(crate) distance: Scalar,
+}
+
+impl HoldManipulation {
+    pub(crate) fn accumulate(&mut self, action: AvianPickupAction) {
+        match action {
+            AvianPickupAction::Rotate { yaw, pitch, roll } => {
+                self.pending_rotation += Vector::new(yaw, pitch, roll);
+            }
+            AvianPickupAction::AdjustDistance(distance) => {
+                self.pending_distance += distance;
+            }
+            AvianPickupAction::SnapRotation => {
+                self.pending_snap = true;
+            }
+            AvianPickupAction::Place => {
+                self.pending_place = true;
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// The shape of a held prop, cached so that it does not need to be rebuilt
+/// every tick. Inserted automatically on the prop while it is held and
+/// rebuilt whenever its colliders change.
+#[derive(Debug, Clone, Component)]
+pub(crate) struct HeldPropShape {
+    /// All colliders of the prop that pass the actor's prop filter,
+    /// combined in the prop's local space.
+    pub(crate) collider: Collider,
+}
+
+impl HeldPropShape {
+    pub(crate) fn new(collider: Collider) -> Self {
+        Self { collider }
+    }
+}
+
 impl Default for HoldError {
     fn default() -> Self {
         Self {
@@ -5,6 +5,7 @@ mod on_add_holding;
 mod on_remove_holding;
 mod set_velocities;
 mod update_error;
+mod update_placement;
 mod update_targets;
 pub(super) fn plugin(app: &mut App) {
     app.configure_sets(
@@ -12,6 +13,7 @@ pub(super) fn plugin(app: &mut App) {
         (
             HoldSystem::UpdateError,
             HoldSystem::SetTargets,
+            HoldSystem::UpdatePlacement,
             HoldSystem::SetVelocities,
         )
             .chain()
@@ -23,6 +25,7 @@ pub(super) fn plugin(app: &mut App) {
         components::plugin,
         update_error::plugin,
         update_targets::plugin,
+        update_placement::plugin,
         set_velocities::plugin,
     ));
 }
@@ -31,9 +34,11 @@ pub(super) fn plugin(app: &mut App) {
 enum HoldSystem {
     UpdateError,
     SetTargets,
+    UpdatePlacement,
     SetVelocities,
 }
 
 pub(super) mod prelude {
-    pub(crate) use super::components::{HoldError, ShadowParams};
+    pub(crate) use super::components::HeldPropShape;
+    pub use super::components::{HoldError, HoldManipulation, ShadowParams};
 }
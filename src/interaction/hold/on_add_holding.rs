@@ -1,59 +1,106 @@
+use avian3d::math::{AdjustPrecision as _, Quaternion};
+
 use super::prelude::HoldError;
-use crate::{prelude::*, prop::PrePickupRotation, verb::Holding};
+use crate::{
+    math::ScalarAsF32 as _,
+    prelude::*,
+    prop::{PrePickupMass, PrePickupRotation},
+    verb::Holding,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_observer(on_add_holding);
 }
 
+/// Attaching is deferred so that state inserted together with [`Holding`],
+/// e.g. when loading a scene with an actor that is holding a prop, is in
+/// place by the time we look at it.
+fn on_add_holding(trigger: On<Add, Holding>, mut commands: Commands) {
+    commands.run_system_cached_with(attach_prop, trigger.entity);
+}
+
 /// CGrabController::AttachEntity
-pub fn on_add_holding(
-    trigger: On<Add, Holding>,
+fn attach_prop(
+    In(actor): In<Entity>,
     mut commands: Commands,
     mut q_actor: Query<(
         &AvianPickupActor,
         &mut AvianPickupActorState,
         &mut HoldError,
         &Holding,
+        Option<&HoldTargetOverride>,
+        Option<&PickupAimOrigin>,
+        Option<&AimRayOverride>,
+        Option<&PickupUpDirection>,
     )>,
-    q_actor_transform: Query<&GlobalTransform>,
-    mut q_prop: Query<(
-        &GlobalTransform,
+    q_transform: Query<&GlobalTransform>,
+    q_prop: Query<(
+        &Rotation,
+        &ComputedMass,
         Option<&Mass>,
         Option<&PickupMassOverride>,
-        Option<&mut PrePickupRotation>,
+        Has<HeldProp>,
     )>,
+    gravity: Res<Gravity>,
 ) {
-    let actor = trigger.entity;
-    let Ok((config, mut state, mut hold_error, holding)) = q_actor.get_mut(actor) else {
+    let Ok((
+        config,
+        mut state,
+        mut hold_error,
+        holding,
+        hold_target,
+        aim_origin,
+        aim_ray,
+        up_direction,
+    )) = q_actor.get_mut(actor)
+    else {
         error!("Actor entity was deleted or in an invalid state. Ignoring.");
         return;
     };
-    let Ok(actor_transform) = q_actor_transform
-        .get(actor)
-        .map(|transform| transform.compute_transform())
+    // The prop keeps its rotation relative to whatever it is held at.
+    let hold_transform = hold_target
+        .and_then(|target| target.resolve(&q_transform))
+        .or_else(|| {
+            let actor_transform = q_transform.get(actor).ok()?;
+            Some(PickupAimOrigin::resolve(
+                aim_origin,
+                aim_ray,
+                actor_transform,
+                PickupUpDirection::resolve(up_direction, config, &gravity),
+                &q_transform,
+            ))
+        });
+    let Some(hold_rotation) = hold_transform.map(|transform| transform.rotation.adjust_precision())
     else {
         error!("Actor entity was deleted or in an invalid state. Ignoring.");
         return;
     };
     let prop = holding.0;
     *state = AvianPickupActorState::Holding(prop);
-    commands.entity(prop).try_insert(HeldProp);
-    let Ok((prop_transform, mass, pickup_mass, pre_pickup_rotation)) = q_prop.get_mut(prop) else {
+    let Ok((prop_rotation, computed_mass, mass, pickup_mass, is_attached)) = q_prop.get(prop)
+    else {
         error!("Prop entity was deleted or in an invalid state. Ignoring.");
         return;
     };
-
-    let actor_space_rotation =
-        prop_rotation_to_actor_space(prop_transform.rotation(), actor_transform);
-    if let Some(mut pre_pickup_rotation) = pre_pickup_rotation {
-        pre_pickup_rotation.0 = actor_space_rotation;
-    } else {
-        commands
-            .entity(prop)
-            .try_insert(PrePickupRotation(actor_space_rotation));
+    if is_attached {
+        // The prop was restored together with the actor, so the cached
+        // pre-pickup state is already there and must not be overwritten.
+        return;
     }
+    commands.entity(prop).try_insert(HeldProp);
+
+    // Using the physics rotation instead of the `GlobalTransform` so that
+    // props that were just moved into place, e.g. when retrieved from a
+    // `PropInventory`, are held with their new rotation.
+    let actor_space_rotation = prop_rotation_to_actor_space(prop_rotation.0, hold_rotation);
+    commands
+        .entity(prop)
+        .try_insert(PrePickupRotation(actor_space_rotation));
 
     // Cache old mass
+    commands
+        .entity(prop)
+        .try_insert(PrePickupMass(computed_mass.value()));
     if let Some(mass) = mass {
         commands.entity(prop).try_insert(NonPickupMass(*mass));
     }
@@ -62,7 +109,7 @@ pub fn on_add_holding(
         .map(|m| m.0)
         .unwrap_or(config.hold.temporary_prop_mass);
 
-    commands.entity(prop).try_insert(Mass(new_mass));
+    commands.entity(prop).try_insert(Mass(new_mass.f32()));
 
     // The original code also does some damping stuff, but then deactivates
     // drag? Seems like a no-op to me
@@ -74,13 +121,11 @@ pub fn on_add_holding(
     // case for HL2 deathmatch, judging by the code? Anyhoot, per
     // discussions on Discord, that code seems to align the prop to
     // the coordinate axes if it is closer than 30 degrees to them.
-    // Does not seem to be that useful.
+    // We do that as an opt-in in `set_targets` instead, see
+    // `AvianPickupActorHoldConfig::rotation_snapping`.
 }
 
 /// TransformAnglesToPlayerSpace
-fn prop_rotation_to_actor_space(rot: Quat, actor: Transform) -> Quat {
-    let world_to_actor = actor.compute_affine().inverse();
-    let rot_to_world = Transform::from_rotation(rot).compute_affine();
-    let local_affine = world_to_actor * rot_to_world;
-    Quat::from_affine3(&local_affine)
+fn prop_rotation_to_actor_space(rot: Quaternion, actor_rotation: Quaternion) -> Quaternion {
+    actor_rotation.inverse() * rot
 }
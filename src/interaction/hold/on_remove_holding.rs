@@ -1,3 +1,4 @@
+use super::prelude::{HeldPropShape, HoldManipulation};
 use crate::{prelude::*, verb::Holding};
 
 pub(super) fn plugin(app: &mut App) {
@@ -8,12 +9,19 @@ fn on_remove_holding(
     trigger: On<Remove, Holding>,
     mut commands: Commands,
     q_actor: Query<&Holding>,
+    mut q_manipulation: Query<&mut HoldManipulation>,
     mut q_prop: Query<(Entity, Option<&NonPickupMass>, Has<HeldProp>)>,
 ) {
     // Safety: We are removing a `Holding` component, so we know that the entity has
     // one.
     let holding = q_actor.get(trigger.entity).unwrap();
     let prop = holding.0;
+    commands
+        .entity(trigger.entity)
+        .remove::<(TractorBeamLength, PlacementCandidate)>();
+    if let Ok(mut manipulation) = q_manipulation.get_mut(trigger.entity) {
+        manipulation.reset();
+    }
     let Ok((entity, non_pickup_mass, has_held_marker)) = q_prop.get_mut(prop) else {
         error!("Prop entity was deleted or in an invalid state. Ignoring.");
         return;
@@ -24,7 +32,7 @@ fn on_remove_holding(
         );
         return;
     }
-    commands.entity(prop).remove::<HeldProp>();
+    commands.entity(prop).remove::<(HeldProp, HeldPropShape)>();
     if let Some(non_pickup_mass) = non_pickup_mass {
         commands.entity(entity).try_insert(non_pickup_mass.0);
         commands.entity(entity).remove::<NonPickupMass>();
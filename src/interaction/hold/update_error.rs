@@ -1,3 +1,4 @@
+use avian3d::math::AdjustPrecision as _;
 use bevy_time::Time;
 
 use super::{HoldSystem, prelude::*};
@@ -15,8 +16,9 @@ pub fn update_error(
     time: Res<Time>,
     q_prop: Query<&GlobalTransform>,
     mut q_actor: Query<(&mut HoldError, &ShadowParams, &Holding)>,
+    length_unit: Res<PhysicsLengthUnit>,
 ) {
-    let dt = time.delta_secs();
+    let dt = time.delta_secs_f64().adjust_precision();
     for (mut hold_error, shadow, holding) in q_actor.iter_mut() {
         let prop = holding.0;
         hold_error.error_time += dt;
@@ -27,12 +29,13 @@ pub fn update_error(
             error!("Prop entity was deleted or in an invalid state. Ignoring.");
             continue;
         };
-        let mut error = (prop_transform.translation() - shadow.target_position).length();
+        let mut error =
+            (prop_transform.translation().adjust_precision() - shadow.target_position).length();
         if hold_error.error_time > 1.0 {
             hold_error.error_time = 1.0;
         }
         let speed = error / hold_error.error_time;
-        if speed > shadow.max_speed {
+        if speed > shadow.max_speed * length_unit.0 {
             // this seems like it would still result in a speed above max_speed
             // but idk.
             error *= 0.5;
@@ -0,0 +1,181 @@
+use avian3d::math::{AdjustPrecision as _, AsF32 as _, Quaternion, Scalar, Vector};
+
+use super::{HoldSystem, prelude::*};
+use crate::{
+    math::rigid_body_compound_collider,
+    prelude::*,
+    verb::{Holding, SetVerb, Verb},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PhysicsSchedule,
+        update_placement.in_set(HoldSystem::UpdatePlacement),
+    );
+}
+
+/// Finds out where the held prop would be placed, either because the actor
+/// wants to place it right now or because the placement should be previewed.
+fn update_placement(
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    mut q_actor: Query<(
+        Entity,
+        &GlobalTransform,
+        &AvianPickupActor,
+        &ShadowParams,
+        &Holding,
+        &mut HoldManipulation,
+        Option<&PickupUpDirection>,
+        Option<&PickupAimOrigin>,
+        Option<&AimRayOverride>,
+    )>,
+    q_prop: Query<(
+        &GlobalTransform,
+        Option<&RigidBodyColliders>,
+        Option<&HeldPropShape>,
+    )>,
+    q_collider: Query<(&GlobalTransform, &Collider, Option<&CollisionLayers>)>,
+    q_transform: Query<&GlobalTransform>,
+    length_unit: Res<PhysicsLengthUnit>,
+    gravity: Res<Gravity>,
+    mut w_placement_failed: MessageWriter<PropPlacementFailed>,
+) {
+    let length_unit = length_unit.0;
+    for (
+        actor,
+        actor_transform,
+        config,
+        shadow,
+        holding,
+        mut manipulation,
+        up_direction,
+        aim_origin,
+        aim_ray,
+    ) in q_actor.iter_mut()
+    {
+        let place = std::mem::take(&mut manipulation.pending_place);
+        if !place && !config.place.preview {
+            continue;
+        }
+        let prop = holding.0;
+        let Ok((prop_transform, rigid_body_colliders, shape)) = q_prop.get(prop) else {
+            error!("Prop entity was deleted or in an invalid state. Ignoring.");
+            continue;
+        };
+        let Some(colliders) = rigid_body_colliders else {
+            error!("Held prop does not have rigid body colliders. Ignoring.");
+            continue;
+        };
+        // The shape is only missing in the tick the prop was picked up.
+        let prop_collider = shape.map(|shape| shape.collider.clone()).or_else(|| {
+            rigid_body_compound_collider(
+                &prop_transform.compute_transform(),
+                colliders.iter(),
+                &q_collider,
+                &config.prop_filter,
+            )
+        });
+        let Some(prop_collider) = prop_collider else {
+            error!("Held prop does not have a collider in its hierarchy. Ignoring.");
+            continue;
+        };
+        let filter = config
+            .obstacle_filter
+            .clone()
+            .with_excluded_entities(colliders.iter());
+        let up = PickupUpDirection::resolve(up_direction, config, &gravity);
+        let (transform, error) = find_placement(
+            &spatial_query,
+            PickupAimOrigin::resolve(aim_origin, aim_ray, actor_transform, up, &q_transform),
+            shadow.target_rotation,
+            &prop_collider,
+            config,
+            length_unit,
+            up,
+            &filter,
+        );
+        let candidate = PlacementCandidate {
+            prop,
+            transform,
+            error,
+        };
+
+        if place {
+            match (candidate.transform, candidate.error) {
+                (Some(transform), None) => {
+                    commands
+                        .entity(actor)
+                        .queue(SetVerb::new(Verb::Place { prop, transform }));
+                }
+                (_, reason) => {
+                    w_placement_failed.write(PropPlacementFailed {
+                        prop,
+                        actor,
+                        reason: reason.unwrap_or(PlacementError::NoSurface),
+                    });
+                }
+            }
+        }
+        if config.place.preview {
+            commands.entity(actor).try_insert(candidate);
+        }
+    }
+}
+
+/// Casts the prop along the actor's line of sight until it rests on a surface.
+fn find_placement(
+    spatial_query: &SpatialQuery,
+    actor_transform: Transform,
+    target_rotation: Quaternion,
+    prop_collider: &Collider,
+    config: &AvianPickupActor,
+    length_unit: Scalar,
+    up: Dir3,
+    filter: &SpatialQueryFilter,
+) -> (Option<Transform>, Option<PlacementError>) {
+    let origin = actor_transform.translation.adjust_precision();
+    let forward = actor_transform.forward();
+    let forward_vector = forward.as_vec3().adjust_precision();
+    let up_vector = up.as_vec3().adjust_precision();
+    let max_distance = config.place.max_distance * length_unit;
+    let Some(surface_hit) = spatial_query.cast_ray(origin, forward, max_distance, true, filter)
+    else {
+        return (None, Some(PlacementError::NoSurface));
+    };
+    let normal = surface_hit.normal.normalize_or(up_vector);
+    if normal.angle_between(up_vector) > config.place.max_surface_angle {
+        return (None, Some(PlacementError::TooSteep));
+    }
+
+    let rotation = if config.place.align_to_surface {
+        let up = target_rotation * Vector::Y;
+        Quaternion::from_rotation_arc(up, normal) * target_rotation
+    } else {
+        target_rotation
+    };
+
+    let Some(prop_hit) = spatial_query.cast_shape(
+        prop_collider,
+        origin,
+        rotation,
+        forward,
+        &ShapeCastConfig {
+            max_distance,
+            ignore_origin_penetration: true,
+            ..default()
+        },
+        filter,
+    ) else {
+        return (None, Some(PlacementError::NoSurface));
+    };
+    let position = origin + forward_vector * prop_hit.distance;
+
+    // If the prop came to rest against something other than a surface it can
+    // stand on, e.g. a wall or a ledge in front of the surface, it's in the way.
+    let contact_normal = prop_hit.normal1.normalize_or(up_vector);
+    let is_obstructed = contact_normal.angle_between(up_vector) > config.place.max_surface_angle;
+    let error = is_obstructed.then_some(PlacementError::Obstructed);
+    let transform = Transform::from_translation(position.f32()).with_rotation(rotation.f32());
+    (Some(transform), error)
+}
@@ -1,6 +1,13 @@
+use avian3d::{
+    math::{AdjustPrecision as _, AsF32 as _, FRAC_PI_2, Quaternion, Scalar, Vector},
+    parry::shape::SharedShape,
+};
+
 use super::{HoldSystem, prelude::*};
+#[cfg(feature = "debug")]
+use crate::debug::HeldPropObstacleHit;
 use crate::{
-    math::rigid_body_compound_collider,
+    math::{rigid_body_compound_collider, up_frame},
     prelude::*,
     prop::PrePickupRotation,
     verb::{Holding, SetVerb, Verb},
@@ -17,131 +24,305 @@ fn set_targets(
     mut q_actor: Query<(
         Entity,
         &GlobalTransform,
-        &AvianPickupActor,
+        Ref<AvianPickupActor>,
         &HoldError,
         &mut ShadowParams,
         &Holding,
+        &mut HoldManipulation,
+        Option<&mut TractorBeamLength>,
+        Option<&PickupUpDirection>,
+        Option<&HoldTargetOverride>,
+        Option<&PickupAimOrigin>,
+        Option<&AimRayOverride>,
+        Option<&PickupHoldAnchor>,
     )>,
     mut q_prop: Query<(
         &GlobalTransform,
         &ComputedCenterOfMass,
-        Option<&RigidBodyColliders>,
+        Option<Ref<RigidBodyColliders>>,
+        Option<&mut HeldPropShape>,
         Option<&PrePickupRotation>,
         Option<&PreferredPickupRotation>,
         Option<&PreferredPickupDistanceOverride>,
         Option<&PitchRangeOverride>,
+        Option<&RotationSnappingOverride>,
     )>,
-
     q_collider: Query<(&GlobalTransform, &Collider, Option<&CollisionLayers>)>,
+    q_changed_collider: Query<
+        (),
+        Or<(
+            Changed<Collider>,
+            Changed<CollisionLayers>,
+            Changed<ColliderOf>,
+        )>,
+    >,
+    q_moved_collider: Query<(), Changed<Transform>>,
+    q_transform: Query<&GlobalTransform>,
+    #[cfg(feature = "debug")] mut q_obstacle_hit: Query<&mut HeldPropObstacleHit>,
+    length_unit: Res<PhysicsLengthUnit>,
+    gravity: Res<Gravity>,
 ) {
-    let max_error = 0.3048; // 12 inches in the source engine
-    for (actor, actor_transform, config, hold_error, mut shadow, holding) in q_actor.iter_mut() {
+    let length_unit = length_unit.0;
+    for (
+        actor,
+        actor_transform,
+        config,
+        hold_error,
+        mut shadow,
+        holding,
+        mut manipulation,
+        beam_length,
+        up_direction,
+        hold_target_override,
+        aim_origin,
+        aim_ray,
+        hold_anchor,
+    ) in q_actor.iter_mut()
+    {
         let prop = holding.0;
-        if hold_error.error > max_error {
+        if hold_error.error > HoldError::MAX * length_unit {
             commands
                 .entity(actor)
                 .queue(SetVerb::new(Verb::Drop { prop, forced: true }));
             continue;
         }
-        let actor_transform = actor_transform.compute_transform();
+        // Props are held in front of the hold anchor, in the direction the actor aims.
+        // Yaw, pitch and roll are relative to the actor's up direction.
+        let up = PickupUpDirection::resolve(up_direction, &config, &gravity);
+        let aim = PickupAimOrigin::resolve(aim_origin, aim_ray, actor_transform, up, &q_transform);
+        let actor_transform =
+            PickupHoldAnchor::resolve(hold_anchor, aim, actor_transform, &q_transform);
+        let actor_position = actor_transform.translation.adjust_precision();
+        let is_config_changed = config.is_changed();
+        let config = config.into_inner();
 
         let Ok((
             prop_transform,
             prop_center_of_mass,
             rigid_body_colliders,
+            shape,
             pre_pickup_rotation,
             preferred_rotation,
             preferred_distance,
             clamp_pitch,
+            rotation_snapping,
         )) = q_prop.get_mut(prop)
         else {
             error!("Prop entity was deleted or in an invalid state. Ignoring.");
             continue;
         };
         let prop_transform = prop_transform.compute_transform();
+        let prop_rotation = prop_transform.rotation.adjust_precision();
         let pitch_range = clamp_pitch
             .map(|c| &c.0)
             .unwrap_or(&config.hold.pitch_range);
-        let (actor_yaw, actor_pitch, actor_roll) = actor_transform.rotation.to_euler(EulerRot::YXZ);
+        let up_frame = up_frame(up, actor_transform.rotation.adjust_precision());
+        let (actor_yaw, actor_pitch, actor_roll) = (up_frame.inverse()
+            * actor_transform.rotation.adjust_precision())
+        .to_euler(EulerRot::YXZ);
         let actor_to_prop_pitch = actor_pitch.clamp(*pitch_range.start(), *pitch_range.end());
-        let clamped_rotation =
-            Quat::from_euler(EulerRot::YXZ, actor_yaw, actor_to_prop_pitch, actor_roll);
-        let forward = Transform::from_rotation(clamped_rotation).forward();
-        // We can't cast a ray wrt an entire rigid body out of the box,
-        // so we manually collect all colliders in the hierarchy and
-        // construct a compound collider.
+        let clamped_rotation = up_frame
+            * Quaternion::from_euler(EulerRot::YXZ, actor_yaw, actor_to_prop_pitch, actor_roll);
+        // Spatial queries take directions in single precision.
+        let forward = Transform::from_rotation(clamped_rotation.f32()).forward();
+        // A hold target override takes the place of the actor's line of sight.
+        let hold_target = hold_target_override.and_then(|target| target.resolve(&q_transform));
+        let hold_rotation = hold_target.map_or(clamped_rotation, |target| {
+            target.rotation.adjust_precision()
+        });
+        let hold_target_position = match hold_target {
+            Some(target) => Some(target.translation.adjust_precision()),
+            None if config.hold.mode == AvianPickupHoldMode::GroundPlane => {
+                Some(ground_plane_target(
+                    aim,
+                    up,
+                    config.hold.ground_plane_height * length_unit,
+                    config.interaction_distance * length_unit,
+                ))
+            }
+            None => None,
+        };
         let Some(colliders) = rigid_body_colliders else {
             error!("Held prop does not have rigid body colliders. Ignoring.");
             continue;
         };
-        let prop_collider = rigid_body_compound_collider(
-            &prop_transform,
-            colliders.iter(),
-            &q_collider,
-            &config.prop_filter,
-        );
-        let Some(prop_collider) = prop_collider else {
-            error!("Held prop does not have a collider in its hierarchy. Ignoring.");
-            continue;
+        // The colliders of the rigid body only need to be combined again if
+        // something about them changed.
+        let is_shape_stale = is_config_changed
+            || colliders.is_changed()
+            || colliders.iter().any(|collider| {
+                q_changed_collider.contains(collider)
+                    // The transform of a collider on the rigid body itself
+                    // changes whenever the prop moves.
+                    || (collider != prop && q_moved_collider.contains(collider))
+            });
+        let shape = match shape {
+            Some(shape) if !is_shape_stale => shape.clone(),
+            shape => {
+                // We can't cast a ray wrt an entire rigid body out of the box,
+                // so we manually collect all colliders in the hierarchy and
+                // construct a compound collider.
+                let prop_collider = rigid_body_compound_collider(
+                    &prop_transform,
+                    colliders.iter(),
+                    &q_collider,
+                    &config.prop_filter,
+                );
+                let Some(prop_collider) = prop_collider else {
+                    error!("Held prop does not have a collider in its hierarchy. Ignoring.");
+                    continue;
+                };
+                let new_shape = HeldPropShape::new(prop_collider);
+                match shape {
+                    Some(mut shape) => {
+                        *shape = new_shape.clone();
+                    }
+                    None => {
+                        commands.entity(prop).try_insert(new_shape.clone());
+                    }
+                }
+                new_shape
+            }
         };
         let prop_radius_wrt_direction =
-            collider_get_extent(&prop_collider, prop_transform.rotation, -forward);
-        let Some(prop_radius_wrt_direction) = prop_radius_wrt_direction else {
-            error!(
-                "Failed to get collider extent: Parry failed to find a hit with its AABB. Ignoring prop."
-            );
-            continue;
-        };
+            collider_get_extent(&shape.collider, prop_rotation, -forward);
 
         let min_non_penetrating_distance = prop_radius_wrt_direction;
-        let min_distance = min_non_penetrating_distance + config.hold.min_distance;
+        let min_distance = min_non_penetrating_distance + config.hold.min_distance * length_unit;
         // The 2013 code now additionally does `min_distance = (min_distance * 2) + 24
         // inches` That seems straight up bizarre, so I refuse to do that.
-        let preferred_distance = preferred_distance
-            .map(|d| d.0)
-            .unwrap_or(config.hold.preferred_distance)
-            + min_non_penetrating_distance;
+        let pending_distance = std::mem::take(&mut manipulation.pending_distance)
+            * config.manipulation.distance_sensitivity
+            * length_unit;
+        let distance_range = &config.manipulation.distance_range;
+        let (min_manipulated_distance, max_manipulated_distance) = (
+            distance_range.start() * length_unit,
+            distance_range.end() * length_unit,
+        );
+        let preferred_distance = match (config.hold.mode, beam_length) {
+            (AvianPickupHoldMode::TractorBeam, Some(mut beam_length)) => {
+                if pending_distance != 0.0 {
+                    beam_length.0 = (beam_length.0 + pending_distance)
+                        .clamp(min_manipulated_distance, max_manipulated_distance);
+                }
+                beam_length
+                    .0
+                    .clamp(0.0, config.interaction_distance * length_unit)
+            }
+            (AvianPickupHoldMode::TractorBeam, None) => {
+                // Latch onto the prop right where it was grabbed. The beam ends
+                // at the edge of the prop, not at its center of mass.
+                let center_of_mass = prop_transform.translation.adjust_precision()
+                    + prop_rotation
+                        * (prop_transform.scale.adjust_precision() * prop_center_of_mass.0);
+                let grabbed_distance = (center_of_mass - actor_position)
+                    .dot(forward.as_vec3().adjust_precision())
+                    - prop_radius_wrt_direction;
+                let beam_length =
+                    grabbed_distance.clamp(0.0, config.interaction_distance * length_unit);
+                commands
+                    .entity(actor)
+                    .try_insert(TractorBeamLength(beam_length));
+                beam_length
+            }
+            _ => {
+                let preferred_distance = preferred_distance
+                    .map_or(config.hold.preferred_distance, |d| d.0)
+                    * length_unit;
+                if pending_distance != 0.0 {
+                    let adjusted_distance =
+                        (preferred_distance + manipulation.distance + pending_distance)
+                            .clamp(min_manipulated_distance, max_manipulated_distance);
+                    manipulation.distance = adjusted_distance - preferred_distance;
+                }
+                preferred_distance + manipulation.distance
+            }
+        } + min_non_penetrating_distance;
         // The 2013 code does `max_distance = preferred_distance + min_distance`
         // which means that `preferred_distance` is the distance between the prop's
         // edge and the actors's edge. Expect psyche, actually `min_distance` gets
         // deduced again at some point!
         let max_distance = preferred_distance.max(min_distance);
-        let Some(actor_space_rotation) = preferred_rotation
+        let Some(unmanipulated_rotation) = preferred_rotation
             .map(|preferred| preferred.0)
             .or_else(|| pre_pickup_rotation.map(|pre| pre.0))
         else {
             error!("Held prop does not have a preferred or pre-pickup rotation. Ignoring.");
             continue;
         };
+        let pending_rotation = std::mem::take(&mut manipulation.pending_rotation)
+            * config.manipulation.rotation_sensitivity;
+        if pending_rotation != Vector::ZERO {
+            let delta_rotation = actor_space_manipulation(
+                pending_rotation,
+                hold_rotation,
+                up,
+                config.manipulation.rotation_space,
+            );
+            manipulation.rotation = (delta_rotation * manipulation.rotation).normalize();
+        }
+        let actor_space_rotation = manipulation.rotation * unmanipulated_rotation;
+
         // orient the prop wrt the actor
         // The 2013 code uses the non-clamped code here, resulting in the prop
         // rotating when looking further up than the clamp allows.
         // Looks weird imo, so we use the clamped rotation.
-        let clamped_actor_transform = actor_transform.with_rotation(clamped_rotation);
-        shadow.target_rotation =
-            prop_rotation_from_actor_space(actor_space_rotation, clamped_actor_transform);
+        let target_rotation = prop_rotation_from_actor_space(actor_space_rotation, hold_rotation);
+        let rotation_snapping = rotation_snapping
+            .map(|snapping| snapping.0)
+            .unwrap_or(config.hold.rotation_snapping);
+        shadow.target_rotation = if std::mem::take(&mut manipulation.pending_snap) {
+            let increment = rotation_snapping.map_or(FRAC_PI_2, |snapping| snapping.increment);
+            let snapped_rotation = snap_rotation(target_rotation, increment, up_frame);
+            // Bake the snapped rotation into the manipulation so that the prop keeps it.
+            // `A * M * R = S` <=> `M = A⁻¹ * S * R⁻¹`
+            manipulation.rotation =
+                (hold_rotation.inverse() * snapped_rotation * unmanipulated_rotation.inverse())
+                    .normalize();
+            snapped_rotation
+        } else if let Some(snapping) = rotation_snapping {
+            // Corresponds to `AlignAngles`, which the 2013 code only uses for some props.
+            let snapped_rotation = snap_rotation(target_rotation, snapping.increment, up_frame);
+            if target_rotation.angle_between(snapped_rotation) <= snapping.threshold {
+                snapped_rotation
+            } else {
+                target_rotation
+            }
+        } else {
+            target_rotation
+        };
 
         // Without some offset, the target position is pointing to the origin of the prop, which is often at its "feet".
         // This looks really weird when holding, so let's hold it at the center of mass instead.
         // Note that the following calculation is distinct from just `prop_center_of_mass.0`,
         // as that one would be the offset if the prop had no rotation.
-        let global_center_of_mass = prop_transform.transform_point(prop_center_of_mass.0);
-        let center_of_mass_offset = global_center_of_mass - prop_transform.translation;
+        let center_of_mass_offset =
+            prop_rotation * (prop_transform.scale.adjust_precision() * prop_center_of_mass.0);
         // Adjusting the actor's transform to the center of mass of the prop might
         // seem backwards, but it's mathematically identical to offsetting the result
         // of any calculation by the center of mass offset. This just does it at the "input"
         // instead of the "output" of the calculation.
-        let center_of_mass_adjusted_actor_transform =
-            actor_transform.translation - center_of_mass_offset;
+        let center_of_mass_adjusted_actor_transform = actor_position - center_of_mass_offset;
+
+        // With a hold target override or on the ground plane, the prop moves towards
+        // the target instead and only stops short of it when something is in the way.
+        let (direction, max_distance) = match hold_target_position {
+            Some(target_position) => {
+                let offset = target_position - actor_position;
+                (Dir3::new(offset.f32()).unwrap_or(forward), offset.length())
+            }
+            None => (forward, max_distance),
+        };
+        let direction_vector = direction.as_vec3().adjust_precision();
 
         let terrain_hit = spatial_query.cast_shape(
-            &prop_collider,
+            &shape.collider,
             center_of_mass_adjusted_actor_transform,
             // more stable results if we use the prop' actual rotation instead of the target rotation
-            prop_transform.rotation,
-            forward,
+            prop_rotation,
+            direction,
             &ShapeCastConfig {
-                max_distance: f32::MAX,
+                max_distance: Scalar::MAX,
                 ignore_origin_penetration: false,
                 ..default()
             },
@@ -150,10 +331,22 @@ fn set_targets(
                 .clone()
                 .with_excluded_entities(colliders.iter()),
         );
+        #[cfg(feature = "debug")]
+        if let Ok(mut obstacle_hit) = q_obstacle_hit.get_mut(actor) {
+            obstacle_hit.0 = terrain_hit
+                .as_ref()
+                .map(|hit| actor_position + direction_vector * hit.distance);
+        }
         let distance = if let Some(terrain_hit) = terrain_hit {
             let toi = terrain_hit.distance;
             let fraction = toi / max_distance;
-            if fraction < 0.5 {
+            // Snapping the prop right in front of the actor feels nice when carrying it,
+            // but would make a long tractor beam or a hand jump around whenever
+            // something crosses it.
+            if fraction < 0.5
+                && config.hold.mode == AvianPickupHoldMode::Carry
+                && hold_target_position.is_none()
+            {
                 min_distance.min(toi)
             } else {
                 max_distance.min(toi)
@@ -164,80 +357,186 @@ fn set_targets(
         // Pretty sure we don't need to go through the CalcClosestPointOnLine song and
         // dance since we already have made sure that the prop has a sensible minimum
         // distance
-        shadow.target_position = center_of_mass_adjusted_actor_transform + forward * distance;
+        shadow.target_position =
+            center_of_mass_adjusted_actor_transform + direction_vector * distance;
     }
 }
 
-/// The original code gets the support point of the collider in the direction,
-/// but we can only do that for convex shapes in parry. Notably, compound shapes
-/// made of convex shapes are not supported.\
-/// So, we instead just cast a ray in the direction and get the hit point.
-/// Since the original code multiplies the direction by the dot product of
-/// the direction and the support point, it looks like the result is the same.
-/// That's why we just return the TOI directly.
-/// Note that we just use the AABB of the compound collider here, which is
-/// not the exact convex hull, but should be close enough.
-fn collider_get_extent(collider: &Collider, rotation: Quat, dir: Dir3) -> Option<f32> {
-    let aabb = collider.aabb(Vec3::ZERO, Quat::IDENTITY);
-    let aabb_lengths = aabb.size();
-    let aabb_collider = Collider::cuboid(aabb_lengths.x, aabb_lengths.y, aabb_lengths.z);
-
-    const TRANSLATION: Vec3 = Vec3::ZERO;
-    const RAY_ORIGIN: Vec3 = Vec3::ZERO;
-    // We cast from inside the collider, so we don't care about a max TOI
-    const MAX_TOI: f32 = f32::MAX;
-    // Needs to be false to not just get the origin back
-    const SOLID: bool = false;
-
-    let hit = aabb_collider.cast_ray(
-        TRANSLATION,
-        rotation,
-        RAY_ORIGIN,
-        dir.into(),
-        MAX_TOI,
-        SOLID,
-    );
-    hit.map(|(toi, _normal)| toi)
+/// How far the collider reaches from its origin in the direction `dir`,
+/// i.e. the distance of its support point along `dir`.\
+/// Parry only provides support maps for convex shapes, so for compound shapes
+/// we take the maximum over all convex parts. Parts without a support map
+/// fall back to their AABB.
+fn collider_get_extent(collider: &Collider, rotation: Quaternion, dir: Dir3) -> Scalar {
+    let local_dir = rotation.inverse() * dir.as_vec3().adjust_precision();
+    let shape = collider.shape_scaled();
+    match shape.as_compound() {
+        Some(compound) => compound
+            .shapes()
+            .iter()
+            .map(|(isometry, part)| {
+                let translation = isometry.translation;
+                let rotation = isometry.rotation;
+                let part_dir = rotation.inverse() * local_dir;
+                translation.dot(local_dir) + shape_get_extent(part, part_dir)
+            })
+            .fold(Scalar::NEG_INFINITY, Scalar::max),
+        None => shape_get_extent(shape, local_dir),
+    }
+}
+
+/// Distance of the support point of a non-compound `shape` along `dir`,
+/// both in the local space of the shape.
+fn shape_get_extent(shape: &SharedShape, dir: Vector) -> Scalar {
+    match shape.as_support_map() {
+        Some(support_map) => support_map.local_support_point(dir).dot(dir),
+        None => {
+            let aabb = Collider::from(shape.clone()).aabb(Vector::ZERO, Quaternion::IDENTITY);
+            aabb.center().dot(dir) + (aabb.size() / 2.0).dot(dir.abs())
+        }
+    }
+}
+
+/// Converts yaw, pitch and roll requested by [`AvianPickupAction::Rotate`]
+/// into a rotation in actor space.
+fn actor_space_manipulation(
+    yaw_pitch_roll: Vector,
+    actor_rotation: Quaternion,
+    up: Dir3,
+    space: AvianPickupRotationSpace,
+) -> Quaternion {
+    let Vector {
+        x: yaw,
+        y: pitch,
+        z: roll,
+    } = yaw_pitch_roll;
+    match space {
+        AvianPickupRotationSpace::Actor => Quaternion::from_euler(EulerRot::YXZ, yaw, pitch, roll),
+        AvianPickupRotationSpace::WorldYaw => {
+            // A world space rotation `W` applied to a prop held with the actor space rotation `R`
+            // results in `W * A * R`, which is the same as `A * (A⁻¹ * W * A) * R`.
+            let world_yaw = Quaternion::from_axis_angle(up.as_vec3().adjust_precision(), yaw);
+            let actor_space_yaw = actor_rotation.inverse() * world_yaw * actor_rotation;
+            actor_space_yaw * Quaternion::from_euler(EulerRot::YXZ, 0.0, pitch, roll)
+        }
+    }
+}
+
+/// Rounds the yaw, pitch and roll of `rotation` relative to `up_frame` to the
+/// nearest multiple of `increment`.
+fn snap_rotation(rotation: Quaternion, increment: Scalar, up_frame: Quaternion) -> Quaternion {
+    let snap = |angle: Scalar| (angle / increment).round() * increment;
+    let (yaw, pitch, roll) = (up_frame.inverse() * rotation).to_euler(EulerRot::YXZ);
+    up_frame * Quaternion::from_euler(EulerRot::YXZ, snap(yaw), snap(pitch), snap(roll))
+}
+
+/// Where the ray the actor aims along crosses the plane `height` above the
+/// ground plane, which goes through the origin and is perpendicular to `up`.
+/// The ray is followed for at most `max_distance`, so aiming at the horizon
+/// puts the target on the plane right below that point instead.
+fn ground_plane_target(aim: Transform, up: Dir3, height: Scalar, max_distance: Scalar) -> Vector {
+    let origin = aim.translation.adjust_precision();
+    let direction = aim.forward().as_vec3().adjust_precision();
+    let up = up.as_vec3().adjust_precision();
+    let height_above_plane = origin.dot(up) - height;
+    let descent = -direction.dot(up);
+    let distance = if descent > 0.0 {
+        (height_above_plane / descent).clamp(0.0, max_distance)
+    } else {
+        max_distance
+    };
+    let point = origin + direction * distance;
+    point - up * (point.dot(up) - height)
 }
 
 /// TransformAnglesFromPlayerSpace
-fn prop_rotation_from_actor_space(rot: Quat, actor: Transform) -> Quat {
-    let actor_matrix = actor.compute_affine();
-    let rot_to_actor = Transform::from_rotation(rot).compute_affine();
-    let out_affine = actor_matrix * rot_to_actor;
-    Quat::from_affine3(&out_affine)
+fn prop_rotation_from_actor_space(rot: Quaternion, actor_rotation: Quaternion) -> Quaternion {
+    actor_rotation * rot
 }
 
 #[cfg(test)]
 mod test {
+    use avian3d::math::FRAC_1_SQRT_2;
+
     use super::*;
 
     #[test]
     fn test_collide_get_extent() {
         let collider = Collider::capsule(0.3, 1.2);
-        let rot = Quat::from_euler(EulerRot::YXZ, -0.014999974, -0.07314853, 0.);
+        let rot = Quaternion::from_euler(EulerRot::YXZ, -0.014999974, -0.07314853, 0.);
         let dir = Vec3::new(0.014959301, -0.073083326, -0.9972137)
             .try_into()
             .unwrap();
-        let extent = collider_get_extent(&collider, rot, dir).unwrap();
-        assert!(extent > 0.0);
+        let extent = collider_get_extent(&collider, rot, dir);
+        assert!(extent > 0.29);
         assert!(extent < 0.6);
     }
 
     #[test]
-    #[ignore = "Parry bug"]
-    fn test_collider_get_extent_manual() {
-        let collider = Collider::capsule(0.3, 1.2);
-        let rotation = Quat::from_euler(EulerRot::YXZ, -0.014999974, -0.07314853, 0.);
-        let dir = Vec3::new(0.014959301, -0.073083326, -0.9972137);
-
-        const TRANSLATION: Vec3 = Vec3::ZERO;
-        const ORIGIN: Vec3 = Vec3::ZERO;
-        // We cast from inside the collider, so we don't care about a max TOI
-        const MAX_TOI: f32 = f32::MAX;
-        // Needs to be false to not just get the origin back
-        const SOLID: bool = false;
-        let hit = collider.cast_ray(TRANSLATION, rotation, ORIGIN, dir, MAX_TOI, SOLID);
-        assert!(hit.is_some());
+    fn test_collider_get_extent_rotated() {
+        // A long stick held lengthwise only reaches half of its width
+        // towards the actor.
+        let collider = Collider::cuboid(0.1, 0.1, 2.0);
+        let rotation = Quaternion::from_rotation_y(FRAC_PI_2);
+        let extent = collider_get_extent(&collider, rotation, Dir3::Z);
+        assert!((extent - 0.05).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_collider_get_extent_compound() {
+        // An L-shape whose AABB reaches much further along the diagonal
+        // than the shape itself.
+        let collider = Collider::compound(vec![
+            (
+                Vector::new(0.5, 0.0, 0.0),
+                Quaternion::IDENTITY,
+                Collider::sphere(0.5),
+            ),
+            (
+                Vector::new(0.0, 0.5, 0.0),
+                Quaternion::IDENTITY,
+                Collider::sphere(0.5),
+            ),
+        ]);
+        let dir = Dir3::new(Vec3::new(1.0, 1.0, 0.0)).unwrap();
+        let extent = collider_get_extent(&collider, Quaternion::IDENTITY, dir);
+        let expected = 0.5 * FRAC_1_SQRT_2 + 0.5;
+        assert!((extent - expected).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_ground_plane_target() {
+        let aim =
+            Transform::from_xyz(0.0, 10.0, 0.0).looking_to(Vec3::new(0.0, -1.0, -1.0), Vec3::Y);
+        let target = ground_plane_target(aim, Dir3::Y, 1.0, 100.0);
+        assert!(target.distance(Vector::new(0.0, 1.0, -9.0)) < 1e-4);
+    }
+
+    #[test]
+    fn test_ground_plane_target_at_the_horizon() {
+        let aim = Transform::from_xyz(0.0, 10.0, 0.0).looking_to(Vec3::NEG_Z, Vec3::Y);
+        let target = ground_plane_target(aim, Dir3::Y, 1.0, 20.0);
+        assert!(target.distance(Vector::new(0.0, 1.0, -20.0)) < 1e-4);
+    }
+
+    #[test]
+    fn test_snap_rotation() {
+        let rotation = Quaternion::from_euler(
+            EulerRot::YXZ,
+            Scalar::to_radians(80.0),
+            Scalar::to_radians(5.0),
+            Scalar::to_radians(-10.0),
+        );
+        let snapped = snap_rotation(rotation, FRAC_PI_2, Quaternion::IDENTITY);
+        assert!(snapped.abs_diff_eq(Quaternion::from_rotation_y(FRAC_PI_2), 1e-5));
+    }
+
+    #[test]
+    fn test_snap_rotation_relative_to_up() {
+        // Standing on a wall whose up direction is +X.
+        let frame = up_frame(Dir3::X, Quaternion::IDENTITY);
+        let rotation = frame * Quaternion::from_rotation_y(Scalar::to_radians(80.0));
+        let snapped = snap_rotation(rotation, FRAC_PI_2, frame);
+        assert!(snapped.abs_diff_eq(frame * Quaternion::from_rotation_y(FRAC_PI_2), 1e-5));
     }
 }
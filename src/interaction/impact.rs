@@ -0,0 +1,96 @@
+use avian3d::math::Vector;
+use bevy_time::Time;
+
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PhysicsSchedule,
+        track_impacts.in_set(AvianPickupSystem::First),
+    );
+}
+
+/// Sends a [`ThrownPropImpact`] for every collision that started during the
+/// last physics step and involves a [`ThrownProp`].
+/// Runs before the next step so that the velocity cached in the
+/// [`ThrownProp`] is still the one from before the impact.
+fn track_impacts(
+    mut commands: Commands,
+    time: Res<Time>,
+    collisions: Collisions,
+    mut r_collision_start: MessageReader<CollisionStart>,
+    mut q_prop: Query<(
+        Entity,
+        &mut ThrownProp,
+        &LinearVelocity,
+        &ComputedMass,
+        &Position,
+        Has<Sleeping>,
+        Has<HeldProp>,
+    )>,
+    q_velocity: Query<&LinearVelocity>,
+    mut w_impact_event: MessageWriter<ThrownPropImpact>,
+) {
+    for event in r_collision_start.read() {
+        let sides = [
+            (event.body1, event.collider1, event.body2, event.collider2),
+            (event.body2, event.collider2, event.body1, event.collider1),
+        ];
+        for (prop_body, prop_collider, other_body, other_collider) in sides {
+            let Some(prop) = prop_body else {
+                continue;
+            };
+            let Ok((_, thrown, _, mass, position, ..)) = q_prop.get(prop) else {
+                continue;
+            };
+            let other_velocity = other_body
+                .and_then(|body| q_velocity.get(body).ok())
+                .map_or(Vector::ZERO, |velocity| velocity.0);
+            let relative_velocity = thrown.previous_velocity - other_velocity;
+
+            let manifold = collisions
+                .get(prop_collider, other_collider)
+                .and_then(|pair| Some((pair.collider1, pair.manifolds.first()?)));
+            let (normal, point) = match manifold {
+                Some((collider1, manifold)) => {
+                    // Manifold normals point from the first collider to the second one.
+                    let normal = if collider1 == prop_collider {
+                        -manifold.normal
+                    } else {
+                        manifold.normal
+                    };
+                    let point = manifold
+                        .points
+                        .first()
+                        .map_or(position.0, |contact| contact.point);
+                    (normal, point)
+                }
+                None => (-relative_velocity.normalize_or_zero(), position.0),
+            };
+            let impact_speed = (-relative_velocity.dot(normal)).max(0.0);
+            w_impact_event.write(ThrownPropImpact {
+                prop,
+                thrower: thrown.thrower,
+                other: other_body.unwrap_or(other_collider),
+                impact_speed,
+                kinetic_energy: 0.5 * mass.value() * impact_speed * impact_speed,
+                point,
+                normal,
+            });
+        }
+    }
+
+    let now = time.elapsed_secs();
+    for (prop, mut thrown, velocity, _, _, is_sleeping, is_held) in q_prop.iter_mut() {
+        if is_sleeping || is_held || now - thrown.at > thrown.timeout {
+            commands.entity(prop).try_remove::<ThrownProp>();
+            for &collider in &thrown.collision_events_enabled {
+                commands
+                    .entity(collider)
+                    .try_remove::<CollisionEventsEnabled>();
+            }
+        } else {
+            thrown.previous_velocity = velocity.0;
+        }
+    }
+}
@@ -1,12 +1,33 @@
 use bevy_app::prelude::*;
 
+mod actor_velocity;
+mod attribution;
 mod drop;
+mod freeze;
 mod hold;
+mod impact;
+mod place;
 mod pull;
+mod stash;
 mod throw;
 
-pub(crate) use self::hold::prelude::*;
+#[cfg(feature = "debug")]
+pub(crate) use self::pull::{
+    can_pull, find_prop_in_cone, find_prop_in_trace, has_line_of_sight, is_pullable,
+};
+pub use self::{actor_velocity::ActorVelocity, hold::prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((hold::plugin, pull::plugin, drop::plugin, throw::plugin));
+    app.add_plugins((
+        actor_velocity::plugin,
+        attribution::plugin,
+        hold::plugin,
+        impact::plugin,
+        pull::plugin,
+        drop::plugin,
+        throw::plugin,
+        freeze::plugin,
+        place::plugin,
+        stash::plugin,
+    ));
 }
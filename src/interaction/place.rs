@@ -0,0 +1,45 @@
+use avian3d::math::{AdjustPrecision as _, Vector};
+use bevy_time::Time;
+
+use crate::{prelude::*, verb::Placing};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(PhysicsSchedule, place.in_set(HandleVerbSystem::Place));
+}
+
+/// Moves the prop to the placement location found while it was held and
+/// releases it there without any velocity.
+fn place(
+    mut commands: Commands,
+    mut q_actor: Query<(Entity, &mut AvianPickupActorState, &mut Cooldown, &Placing)>,
+    mut q_prop: Query<(
+        &mut Position,
+        &mut Rotation,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+    mut w_place_event: MessageWriter<PropPlaced>,
+    time: Res<Time>,
+) {
+    for (actor, mut state, mut cooldown, placing) in q_actor.iter_mut() {
+        let prop = placing.prop;
+        *state = AvianPickupActorState::Idle;
+        cooldown.drop();
+        commands.entity(actor).remove::<Placing>();
+        commands.entity(prop).try_insert(LastHandledBy {
+            actor,
+            action: HandlingAction::Placed,
+            at: time.elapsed_secs(),
+        });
+        let Ok((mut position, mut rotation, mut velocity, mut angvel)) = q_prop.get_mut(prop)
+        else {
+            error!("Prop entity was deleted or in an invalid state. Ignoring.");
+            continue;
+        };
+        position.0 = placing.transform.translation.adjust_precision();
+        rotation.0 = placing.transform.rotation.adjust_precision();
+        velocity.0 = Vector::ZERO;
+        angvel.0 = Vector::ZERO;
+        w_place_event.write(PropPlaced { actor, prop });
+    }
+}
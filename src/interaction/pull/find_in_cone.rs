@@ -1,81 +1,95 @@
 use super::Prop;
 use crate::{math::METERS_PER_INCH, prelude::*};
+use avian3d::math::{AdjustPrecision as _, AsF32 as _, Scalar, Vector};
 use bevy_ecs::relationship::Relationship as _;
 
 /// Inspired by [`CWeaponPhysCannon::FindObjectInCone`](https://github.com/ValveSoftware/source-sdk-2013/blob/master/mp/src/game/server/hl2/weapon_physcannon.cpp#L2690)
-pub(super) fn find_prop_in_cone(
+///
+/// In contrast to the 2013 code, this first does all the cheap checks for
+/// every rigid body in range and then goes through the remaining ones from
+/// nearest to furthest, so that only as many rays are cast as needed.
+pub(crate) fn find_prop_in_cone(
     spatial_query: &SpatialQuery,
     origin: Transform,
     config: &AvianPickupActor,
+    length_unit: Scalar,
     q_position: &Query<&GlobalTransform>,
-    q_rigid_body: &Query<&RigidBody>,
+    is_prop: impl Fn(Entity) -> bool,
     q_collider_parent: &Query<&ColliderOf>,
+    q_layers: &Query<&CollisionLayers>,
 ) -> Option<Prop> {
-    let is_dynamic = |entity: Entity| {
-        q_rigid_body
-            .get(entity)
-            .is_ok_and(|rigid_body| rigid_body.is_dynamic())
-    };
-
-    const MAGIC_OFFSET_ASK_VALVE: f32 = 1.0 * METERS_PER_INCH;
+    const MAGIC_OFFSET_ASK_VALVE: Scalar = 1.0 * METERS_PER_INCH;
     // Reminder that the actual trace is done with 4 times the
     // configured trace length in the 2013 code, eek
-    let mut nearest_dist = config.interaction_distance + MAGIC_OFFSET_ASK_VALVE;
-    let box_collider = Cuboid::from_size(Vec3::splat(2.0 * nearest_dist)).into();
+    let max_dist = (config.interaction_distance + MAGIC_OFFSET_ASK_VALVE) * length_unit;
+    let max_dot = config.interaction_cone;
+    let origin_position = origin.translation.adjust_precision();
+    let forward = origin.forward().as_vec3().adjust_precision();
 
-    let rigid_bodies = spatial_query
-        .shape_intersections(
-            &box_collider,
-            origin.translation,
-            origin.rotation,
-            &config.prop_filter,
-        )
+    // Querying the broadphase with an AABB is a lot cheaper than
+    // testing an actual shape for intersections.
+    let aabb = ColliderAabb::new(origin_position, Vector::splat(max_dist));
+    let mut rigid_bodies = spatial_query
+        .aabb_intersections_with_aabb(aabb)
         .into_iter()
-        .filter_map(|entity| q_collider_parent.get(entity).ok())
+        .filter(|&collider| {
+            let layers = q_layers.get(collider).copied().unwrap_or_default();
+            config.prop_filter.test(collider, layers)
+        })
+        .filter_map(|collider| q_collider_parent.get(collider).ok())
         .map(|collider_parent| collider_parent.get())
-        .filter(|entity| is_dynamic(*entity))
         .collect::<Vec<_>>();
-    let mut canditate = None;
-
-    for rigid_body in rigid_bodies {
-        let Ok(object_translation) = q_position
-            .get(rigid_body)
-            .map(|transform| transform.translation())
-        else {
-            error!("Prop entity was deleted or in an invalid state. Ignoring.");
-            continue;
-        };
+    // Rigid bodies with multiple colliders only need to be checked once.
+    rigid_bodies.sort_unstable();
+    rigid_bodies.dedup();
 
-        // Closer than other objects
-        let los = object_translation - origin.translation;
-        if los.length_squared() >= nearest_dist * nearest_dist {
-            continue;
-        }
-        let (los, dist) = Dir3::new_and_length(los).expect("Failed to normalize line of sight");
-
-        // Cull to the cone
-        let max_dot = config.interaction_cone;
-        if los.dot(origin.forward().into()) <= max_dot {
-            continue;
-        }
+    let mut candidates = rigid_bodies
+        .into_iter()
+        .filter(|&rigid_body| is_prop(rigid_body))
+        .filter_map(|rigid_body| {
+            let Ok(object_translation) = q_position
+                .get(rigid_body)
+                .map(|transform| transform.translation().adjust_precision())
+            else {
+                error!("Prop entity was deleted or in an invalid state. Ignoring.");
+                return None;
+            };
+            let los = object_translation - origin_position;
+            let dist = los.length();
+            let los = los.try_normalize()?;
+            // Cull to the range and cone
+            (dist < max_dist && los.dot(forward) > max_dot).then_some((rigid_body, los, dist))
+        })
+        .collect::<Vec<_>>();
+    // Ties are broken by entity so that the result does not depend on the
+    // order of the broadphase.
+    candidates
+        .sort_unstable_by(|(a, _, a_dist), (b, _, b_dist)| a_dist.total_cmp(b_dist).then(a.cmp(b)));
 
-        // Make sure it isn't occluded by terrain
-        if let Some(hit) =
-            spatial_query.cast_ray(origin.translation, los, dist, true, &config.obstacle_filter)
-        {
+    // Nearest object that isn't occluded by terrain
+    candidates
+        .into_iter()
+        .find(|&(rigid_body, los, dist)| {
+            // Spatial queries take directions in single precision.
+            let Ok(direction) = Dir3::new(los.f32()) else {
+                return false;
+            };
+            let Some(hit) = spatial_query.cast_ray(
+                origin_position,
+                direction,
+                dist,
+                true,
+                &config.obstacle_filter,
+            ) else {
+                return true;
+            };
             let hit_rigid_body = q_collider_parent
                 .get(hit.entity)
                 .map_or(hit.entity, |collider_parent| collider_parent.get());
-            if hit_rigid_body != rigid_body {
-                continue;
-            }
-        }
-
-        nearest_dist = dist;
-        canditate.replace(Prop {
+            hit_rigid_body == rigid_body
+        })
+        .map(|(rigid_body, _, dist)| Prop {
             entity: rigid_body,
             toi: dist,
-        });
-    }
-    canditate
+        })
 }
@@ -1,33 +1,35 @@
 use super::Prop;
 use crate::prelude::*;
+use avian3d::math::{AdjustPrecision as _, Scalar};
 use bevy_ecs::relationship::Relationship as _;
 /// Inspired by [`CWeaponPhysCannon::FindObjectTrace`](https://github.com/ValveSoftware/source-sdk-2013/blob/master/mp/src/game/server/hl2/weapon_physcannon.cpp#L2470)
-pub(super) fn find_prop_in_trace(
+/// `is_prop` decides which rigid bodies are considered props.
+pub(crate) fn find_prop_in_trace(
     spatial_query: &SpatialQuery,
     origin: Transform,
     config: &AvianPickupActor,
-    q_rigid_body: &Query<&RigidBody>,
+    length_unit: Scalar,
+    is_prop: impl Fn(Entity) -> bool,
     q_collider_parent: &Query<&ColliderOf>,
 ) -> Option<Prop> {
     // Fun fact: Valve lies to you and actually multiplies this by 4 at this point.
-    let test_length = config.interaction_distance;
-    let is_dynamic = |entity: Entity| {
+    let test_length = config.interaction_distance * length_unit;
+    let origin_position = origin.translation.adjust_precision();
+    let origin_rotation = origin.rotation.adjust_precision();
+    let is_prop_collider = |entity: Entity| {
         let Ok(collider_parent) = q_collider_parent.get(entity) else {
             return false;
         };
-        let rigid_body = collider_parent.get();
-        q_rigid_body
-            .get(rigid_body)
-            .is_ok_and(|rigid_body| rigid_body.is_dynamic())
+        is_prop(collider_parent.get())
     };
     let hit = spatial_query
         .cast_ray_predicate(
-            origin.translation,
+            origin_position,
             origin.forward(),
             test_length,
             true,
             &config.prop_filter,
-            &is_dynamic,
+            &is_prop_collider,
         )
         .and_then(|hit| {
             q_collider_parent
@@ -37,7 +39,7 @@ pub(super) fn find_prop_in_trace(
         })
         .filter(|(rigid_body, distance)| {
             if let Some(terrain_hit) = spatial_query.cast_ray(
-                origin.translation,
+                origin_position,
                 origin.forward(),
                 *distance,
                 true,
@@ -60,23 +62,24 @@ pub(super) fn find_prop_in_trace(
         .into()
     } else {
         // This has a half-extent of 4 inches in the 2013 code, which is about 1 cm
-        const MAGIC_HALF_EXTENT_ASK_VALVE: f32 = 0.01;
+        const MAGIC_HALF_EXTENT_ASK_VALVE: Scalar = 0.01;
+        let half_extent = MAGIC_HALF_EXTENT_ASK_VALVE * length_unit;
         let fake_aabb_because_parry_cannot_do_aabb_casts =
-            Cuboid::from_size(Vec3::splat(2. * MAGIC_HALF_EXTENT_ASK_VALVE)).into();
+            Collider::cuboid(2. * half_extent, 2. * half_extent, 2. * half_extent);
         let hit = spatial_query.cast_shape_predicate(
             &fake_aabb_because_parry_cannot_do_aabb_casts,
-            origin.translation,
-            origin.rotation,
+            origin_position,
+            origin_rotation,
             origin.forward(),
             &ShapeCastConfig::from_max_distance(test_length),
             &config.prop_filter,
-            &is_dynamic,
+            &is_prop_collider,
         );
         hit.filter(|hit| {
             if let Some(terrain_hit) = spatial_query.cast_shape(
                 &fake_aabb_because_parry_cannot_do_aabb_casts,
-                origin.translation,
-                origin.rotation,
+                origin_position,
+                origin_rotation,
                 origin.forward(),
                 &ShapeCastConfig::from_max_distance(hit.distance),
                 &config.obstacle_filter,
@@ -87,7 +90,9 @@ pub(super) fn find_prop_in_trace(
             }
         })
         .map(|hit| Prop {
-            entity: hit.entity,
+            entity: q_collider_parent
+                .get(hit.entity)
+                .map_or(hit.entity, |collider_parent| collider_parent.get()),
             toi: hit.distance,
         })
     }
@@ -0,0 +1,66 @@
+use crate::prelude::*;
+use avian3d::math::{AdjustPrecision as _, AsF32 as _, Vector};
+use bevy_ecs::relationship::Relationship as _;
+
+/// Whether nothing in `config.obstacle_filter` blocks the line of sight
+/// between `origin` and the rigid body `prop` at `prop_position`.
+///
+/// The actor's own colliders, i.e. those of its rigid body or of any of its
+/// ancestors that pass `config.actor_filter`, never block the line of sight,
+/// so an origin inside the actor's body works as expected.
+/// The prop counts as visible if any of its colliders can be seen, not just
+/// its origin, so large props peeking out from behind cover can be pulled.
+pub(crate) fn has_line_of_sight(
+    spatial_query: &SpatialQuery,
+    origin: Vector,
+    actor: Entity,
+    prop: Entity,
+    prop_position: Vector,
+    config: &AvianPickupActor,
+    q_collider_parent: &Query<&ColliderOf>,
+    q_layers: &Query<&CollisionLayers>,
+    q_parent: &Query<&ChildOf>,
+    q_rigid_body_colliders: &Query<&RigidBodyColliders>,
+    q_collider: &Query<(&Position, &Rotation, &Collider)>,
+) -> bool {
+    let owner = |collider: Entity| {
+        q_collider_parent
+            .get(collider)
+            .map_or(collider, |collider_parent| collider_parent.get())
+    };
+    let actor_bodies: Vec<_> = std::iter::once(actor)
+        .chain(q_parent.iter_ancestors(actor))
+        .collect();
+    let is_not_actor_collider = |collider: Entity| {
+        let layers = q_layers.get(collider).copied().unwrap_or_default();
+        !(actor_bodies.contains(&owner(collider)) && config.actor_filter.test(collider, layers))
+    };
+    // Aim at the point of every collider closest to the origin, falling back
+    // to the prop's origin.
+    let closest_points = q_rigid_body_colliders
+        .get(prop)
+        .into_iter()
+        .flat_map(|colliders| colliders.iter())
+        .filter_map(|collider| q_collider.get(collider).ok())
+        .map(|(position, rotation, collider)| {
+            collider.project_point(*position, *rotation, origin, true).0
+        });
+    let mut targets = closest_points.chain(std::iter::once(prop_position));
+    targets.any(|target| {
+        let Ok((direction, distance)) = Dir3::new_and_length((target - origin).f32()) else {
+            // The origin is inside the prop.
+            return true;
+        };
+        let Some(hit) = spatial_query.cast_ray_predicate(
+            origin,
+            direction,
+            distance.adjust_precision(),
+            true,
+            &config.obstacle_filter,
+            &is_not_actor_collider,
+        ) else {
+            return true;
+        };
+        owner(hit.entity) == prop
+    })
+}
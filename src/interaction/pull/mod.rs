@@ -1,13 +1,20 @@
+use avian3d::math::{AdjustPrecision as _, Scalar};
+
 use crate::{
     prelude::*,
+    socket::{is_removable, unsocket},
     verb::{Pulling, SetVerb, Verb},
 };
 
 mod can_pull;
 mod find_in_cone;
 mod find_in_trace;
+mod line_of_sight;
 
-use self::{can_pull::*, find_in_cone::*, find_in_trace::*};
+pub(crate) use self::{
+    can_pull::can_pull, find_in_cone::find_prop_in_cone, find_in_trace::find_prop_in_trace,
+    line_of_sight::has_line_of_sight,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(PhysicsSchedule, find_object.in_set(HandleVerbSystem::Pull))
@@ -28,44 +35,148 @@ fn find_object(
             &AvianPickupActor,
             &mut AvianPickupActorState,
             &mut Cooldown,
+            Option<&PickupAimOrigin>,
+            Option<&AimRayOverride>,
+            Option<&PickupHoldAnchor>,
+            Option<&PickupUpDirection>,
         ),
         With<Pulling>,
     >,
     q_collider_parent: Query<&ColliderOf>,
-    mut q_rigid_body: Query<(
-        &RigidBody,
-        &ComputedMass,
-        Forces,
-        &GlobalTransform,
-        Has<HeldProp>,
-    )>,
+    q_layers: Query<&CollisionLayers>,
+    q_parent: Query<&ChildOf>,
+    q_rigid_body_colliders: Query<&RigidBodyColliders>,
+    q_collider: Query<(&Position, &Rotation, &Collider)>,
+    q_rigid_body: Query<(&RigidBody, &ComputedMass, &GlobalTransform, Has<HeldProp>)>,
+    q_rigid_body_type: Query<&RigidBody>,
+    mut q_forces: Query<Forces>,
     q_position: Query<&GlobalTransform>,
+    q_prop_position: Query<&Position>,
+    (q_socketed, q_socket): (Query<&SocketedProp>, Query<&PropSocket>),
+    (length_unit, gravity): (Res<PhysicsLengthUnit>, Res<Gravity>),
+    mut w_unsocketed: MessageWriter<PropUnsocketed>,
 ) {
-    for (actor, actor_transform, config, mut state, mut cooldown) in q_actor.iter_mut() {
-        let actor_transform = actor_transform.compute_transform();
+    let length_unit = length_unit.0;
+    let is_prop = |entity: Entity| is_pullable(entity, &q_rigid_body_type, &q_socketed, &q_socket);
+    let find_prop = |(
+        actor,
+        actor_transform,
+        config,
+        _,
+        _,
+        aim_origin,
+        aim_ray,
+        hold_anchor,
+        up_direction,
+    ): (
+        Entity,
+        &GlobalTransform,
+        &AvianPickupActor,
+        &AvianPickupActorState,
+        &Cooldown,
+        Option<&PickupAimOrigin>,
+        Option<&AimRayOverride>,
+        Option<&PickupHoldAnchor>,
+        Option<&PickupUpDirection>,
+    )| {
+        let up = PickupUpDirection::resolve(up_direction, config, &gravity);
+        let aim = PickupAimOrigin::resolve(aim_origin, aim_ray, actor_transform, up, &q_position);
+        let hold_origin = PickupHoldAnchor::resolve(hold_anchor, aim, actor_transform, &q_position);
         let prop = find_prop_in_trace(
             &spatial_query,
-            actor_transform,
+            aim,
             config,
-            &q_rigid_body.transmute_lens().query(),
+            length_unit,
+            is_prop,
             &q_collider_parent,
         )
         .or_else(|| {
             find_prop_in_cone(
                 &spatial_query,
-                actor_transform,
+                aim,
                 config,
+                length_unit,
                 &q_position,
-                &q_rigid_body.transmute_lens().query(),
+                is_prop,
                 &q_collider_parent,
+                &q_layers,
             )
+        })
+        // The actor aims from somewhere else than where it holds props,
+        // so make sure it can actually reach the prop.
+        .filter(|prop| {
+            hold_origin.translation == aim.translation
+                || q_prop_position.get(prop.entity).is_ok_and(|prop_position| {
+                    has_line_of_sight(
+                        &spatial_query,
+                        hold_origin.translation.adjust_precision(),
+                        actor,
+                        prop.entity,
+                        prop_position.0,
+                        config,
+                        &q_collider_parent,
+                        &q_layers,
+                        &q_parent,
+                        &q_rigid_body_colliders,
+                        &q_collider,
+                    )
+                })
+        });
+        prop.map(|prop| (actor, prop))
+    };
+
+    // Searching is the expensive part, so it can be done for all actors in
+    // parallel. The results are applied one after another afterwards.
+    #[cfg(feature = "parallel")]
+    let found_props = {
+        let mut found_props = bevy_utils::Parallel::<Vec<_>>::default();
+        q_actor.par_iter().for_each(|actor| {
+            if let Some(found) = find_prop(actor) {
+                found_props.borrow_local_mut().push(found);
+            }
         });
-        let Some(prop) = prop else {
+        let mut found_props: Vec<_> = found_props.drain().collect();
+        // Keep the order of side effects independent of the threads.
+        found_props.sort_unstable_by_key(|(actor, _)| *actor);
+        found_props
+    };
+    #[cfg(not(feature = "parallel"))]
+    let found_props = q_actor.iter().filter_map(find_prop).collect::<Vec<_>>();
+
+    for (actor, prop) in found_props {
+        let Ok((
+            _,
+            actor_transform,
+            config,
+            mut state,
+            mut cooldown,
+            aim_origin,
+            aim_ray,
+            hold_anchor,
+            up_direction,
+        )) = q_actor.get_mut(actor)
+        else {
             continue;
         };
+        let up = PickupUpDirection::resolve(up_direction, config, &gravity);
+        let aim = PickupAimOrigin::resolve(aim_origin, aim_ray, actor_transform, up, &q_position);
+        let hold_origin = PickupHoldAnchor::resolve(hold_anchor, aim, actor_transform, &q_position);
 
-        let Ok((_, &mass, mut forces, prop_position, is_already_being_held)) =
-            q_rigid_body.get_mut(prop.entity)
+        if let Ok(&socketed) = q_socketed.get(prop.entity) {
+            // The prop may only become dynamic once the commands are applied,
+            // so it is pulled as usual the next time around.
+            unsocket(
+                &mut commands,
+                prop.entity,
+                socketed,
+                Some(actor),
+                &mut w_unsocketed,
+            );
+            cooldown.pull();
+            continue;
+        }
+
+        let Ok((_, &mass, prop_position, is_already_being_held)) = q_rigid_body.get(prop.entity)
         else {
             // These components might not be present on non-dynamic rigid bodies
             continue;
@@ -75,19 +186,29 @@ fn find_object(
             continue;
         }
 
-        let can_hold = prop.toi <= config.hold.distance_to_allow_holding;
+        let can_hold = match config.hold.mode {
+            AvianPickupHoldMode::Carry => {
+                prop.toi <= config.hold.distance_to_allow_holding * length_unit
+            }
+            // The prop was already found within `interaction_distance`,
+            // so we can latch onto it right away.
+            AvianPickupHoldMode::TractorBeam | AvianPickupHoldMode::GroundPlane => true,
+        };
         if can_hold {
             cooldown.hold();
             commands
                 .entity(actor)
                 .queue(SetVerb::new(Verb::Hold(prop.entity)));
         } else {
-            let direction =
-                (actor_transform.translation - prop_position.translation()).normalize_or_zero();
+            let direction = (hold_origin.translation - prop_position.translation())
+                .adjust_precision()
+                .normalize_or_zero();
             let mass_adjustment = adjust_impulse_for_mass(mass);
-            let pull_impulse = direction * config.pull.impulse * mass_adjustment;
+            let pull_impulse = direction * config.pull.impulse * length_unit * mass_adjustment;
             cooldown.pull();
-            forces.apply_linear_impulse(pull_impulse);
+            if let Ok(mut forces) = q_forces.get_mut(prop.entity) {
+                forces.apply_linear_impulse(pull_impulse);
+            }
             if !matches!(state.as_ref(), AvianPickupActorState::Pulling(..)) {
                 *state = AvianPickupActorState::Pulling(prop.entity);
             }
@@ -96,8 +217,23 @@ fn find_object(
     }
 }
 
+/// Whether the rigid body `entity` is a prop that can be pulled.
+/// Props in a socket may be locked in place as static rigid bodies,
+/// but can still be pulled out if the socket allows it.
+pub(crate) fn is_pullable(
+    entity: Entity,
+    q_rigid_body: &Query<&RigidBody>,
+    q_socketed: &Query<&SocketedProp>,
+    q_socket: &Query<&PropSocket>,
+) -> bool {
+    match q_socketed.get(entity) {
+        Ok(socketed) => is_removable(socketed, q_socket),
+        Err(_) => q_rigid_body.get(entity).is_ok_and(RigidBody::is_dynamic),
+    }
+}
+
 /// Taken from [this snippet](https://github.com/ValveSoftware/source-sdk-2013/blob/master/src/game/server/hl2/weapon_physcannon.cpp#L2607-L2610)
-fn adjust_impulse_for_mass(mass: ComputedMass) -> f32 {
+fn adjust_impulse_for_mass(mass: ComputedMass) -> Scalar {
     if mass.value() < 50.0 {
         (mass.value() + 0.5) * (1.0 / 50.0)
     } else {
@@ -106,10 +242,9 @@ fn adjust_impulse_for_mass(mass: ComputedMass) -> f32 {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-
-struct Prop {
+pub(crate) struct Prop {
     pub entity: Entity,
-    pub toi: f32,
+    pub toi: Scalar,
 }
 
 fn flush_pulling_state(mut q_state: Query<(Mut<AvianPickupActorState>, Has<Pulling>, &Cooldown)>) {
@@ -0,0 +1,216 @@
+use avian3d::math::AdjustPrecision as _;
+#[cfg(feature = "visibility")]
+use bevy_camera::visibility::Visibility;
+use bevy_time::Time;
+
+use crate::{
+    math::rigid_body_compound_collider,
+    prelude::*,
+    prop::PrePickupMass,
+    verb::{Retrieving, SetVerb, Stashing, Verb},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(PhysicsSchedule, stash.in_set(HandleVerbSystem::Stash))
+        .add_systems(PhysicsSchedule, retrieve.in_set(HandleVerbSystem::Retrieve));
+}
+
+/// The [`Visibility`] a prop had before it was stashed and hidden.
+#[cfg(feature = "visibility")]
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+struct PreStashVisibility(Visibility);
+
+/// Takes the prop out of the physics world and puts it into the actor's
+/// inventory.
+/// By the time this runs, the prop's original mass has already been restored
+/// by removing [`Holding`](crate::verb::Holding).
+fn stash(
+    mut commands: Commands,
+    mut q_actor: Query<(
+        Entity,
+        &GlobalTransform,
+        &AvianPickupActor,
+        &mut AvianPickupActorState,
+        &mut Cooldown,
+        &mut PropInventory,
+        &Stashing,
+        Option<&PickupAimOrigin>,
+        Option<&AimRayOverride>,
+        Option<&PickupUpDirection>,
+    )>,
+    mut q_prop: Query<(
+        &Rotation,
+        Option<&PrePickupMass>,
+        Option<&RigidBodyColliders>,
+    )>,
+    q_transform: Query<&GlobalTransform>,
+    #[cfg(feature = "visibility")] q_visibility: Query<&Visibility>,
+    mut w_stash_event: MessageWriter<PropStashed>,
+    time: Res<Time>,
+    gravity: Res<Gravity>,
+) {
+    for (
+        actor,
+        actor_transform,
+        config,
+        mut state,
+        mut cooldown,
+        mut inventory,
+        stashing,
+        aim_origin,
+        aim_ray,
+        up_direction,
+    ) in q_actor.iter_mut()
+    {
+        let prop = stashing.0;
+        *state = AvianPickupActorState::Idle;
+        cooldown.drop();
+        commands.entity(actor).remove::<Stashing>();
+        let Ok((rotation, mass, colliders)) = q_prop.get_mut(prop) else {
+            error!("Prop entity was deleted or in an invalid state. Ignoring.");
+            continue;
+        };
+        let up = PickupUpDirection::resolve(up_direction, config, &gravity);
+        let aim = PickupAimOrigin::resolve(aim_origin, aim_ray, actor_transform, up, &q_transform);
+        inventory.push(PropInventoryEntry {
+            prop,
+            mass: mass.map_or(0.0, |mass| mass.0),
+            rotation: aim.rotation.adjust_precision().inverse() * rotation.0,
+        });
+        commands.entity(prop).try_insert((
+            RigidBodyDisabled,
+            StashedProp { by: actor },
+            LastHandledBy {
+                actor,
+                action: HandlingAction::Stashed,
+                at: time.elapsed_secs(),
+            },
+        ));
+        for collider in colliders.into_iter().flat_map(|colliders| colliders.iter()) {
+            commands.entity(collider).try_insert(ColliderDisabled);
+        }
+        #[cfg(feature = "visibility")]
+        if let Ok(&visibility) = q_visibility.get(prop) {
+            commands
+                .entity(prop)
+                .try_insert((Visibility::Hidden, PreStashVisibility(visibility)));
+        }
+        w_stash_event.write(PropStashed { actor, prop });
+    }
+}
+
+/// Puts the prop back into the physics world at the actor's hold target and
+/// starts holding it right away.
+/// The prop is moved closer to the actor if something is in the way.
+fn retrieve(
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    mut q_actor: Query<(
+        Entity,
+        &GlobalTransform,
+        &AvianPickupActor,
+        &mut Cooldown,
+        &mut PropInventory,
+        &Retrieving,
+        Option<&PickupAimOrigin>,
+        Option<&AimRayOverride>,
+        Option<&PickupHoldAnchor>,
+        Option<&PickupUpDirection>,
+    )>,
+    q_prop: Query<(
+        &GlobalTransform,
+        Option<&RigidBodyColliders>,
+        Option<&PreferredPickupDistanceOverride>,
+    )>,
+    q_collider: Query<(&GlobalTransform, &Collider, Option<&CollisionLayers>)>,
+    q_transform: Query<&GlobalTransform>,
+    #[cfg(feature = "visibility")] q_visibility: Query<&PreStashVisibility>,
+    length_unit: Res<PhysicsLengthUnit>,
+    gravity: Res<Gravity>,
+    mut w_retrieve_event: MessageWriter<PropRetrieved>,
+) {
+    for (
+        actor,
+        actor_transform,
+        config,
+        mut cooldown,
+        mut inventory,
+        retrieving,
+        aim_origin,
+        aim_ray,
+        hold_anchor,
+        up_direction,
+    ) in q_actor.iter_mut()
+    {
+        commands.entity(actor).remove::<Retrieving>();
+        let Some(entry) = inventory.remove(retrieving.0) else {
+            continue;
+        };
+        let prop = entry.prop;
+        let Ok((prop_transform, colliders, preferred_distance)) = q_prop.get(prop) else {
+            error!("Prop entity was deleted or in an invalid state. Ignoring.");
+            continue;
+        };
+        let up = PickupUpDirection::resolve(up_direction, config, &gravity);
+        let aim = PickupAimOrigin::resolve(aim_origin, aim_ray, actor_transform, up, &q_transform);
+        let hold_origin =
+            PickupHoldAnchor::resolve(hold_anchor, aim, actor_transform, &q_transform);
+        let target_rotation = hold_origin.rotation.adjust_precision() * entry.rotation;
+        let preferred_distance = preferred_distance
+            .map(|d| d.0)
+            .unwrap_or(config.hold.preferred_distance)
+            * length_unit.0;
+        let colliders = colliders.into_iter().flat_map(|colliders| colliders.iter());
+        let prop_collider = rigid_body_compound_collider(
+            &prop_transform.compute_transform(),
+            colliders.clone(),
+            &q_collider,
+            &config.prop_filter,
+        );
+        // Stashed colliders are disabled, so the cast only finds other obstacles.
+        let terrain_hit = prop_collider.and_then(|prop_collider| {
+            spatial_query.cast_shape(
+                &prop_collider,
+                hold_origin.translation.adjust_precision(),
+                target_rotation,
+                hold_origin.forward(),
+                &ShapeCastConfig {
+                    max_distance: preferred_distance,
+                    ignore_origin_penetration: false,
+                    ..default()
+                },
+                &config.obstacle_filter,
+            )
+        });
+        let distance = terrain_hit.map_or(preferred_distance, |hit| {
+            hit.distance.min(preferred_distance)
+        });
+        let position = hold_origin.translation.adjust_precision()
+            + hold_origin.forward().as_vec3().adjust_precision() * distance;
+        commands
+            .entity(prop)
+            .try_insert((
+                Position(position),
+                Rotation(target_rotation),
+                LinearVelocity::ZERO,
+                AngularVelocity::ZERO,
+            ))
+            .remove::<(RigidBodyDisabled, StashedProp)>();
+        for collider in colliders {
+            commands.entity(collider).remove::<ColliderDisabled>();
+        }
+        #[cfg(feature = "visibility")]
+        if let Ok(&PreStashVisibility(visibility)) = q_visibility.get(prop) {
+            commands
+                .entity(prop)
+                .try_insert(visibility)
+                .remove::<PreStashVisibility>();
+        }
+
+        cooldown.hold();
+        // A tractor beam latches onto the prop right where it was put, just
+        // like when grabbing it.
+        commands.entity(actor).queue(SetVerb::new(Verb::Hold(prop)));
+        w_retrieve_event.write(PropRetrieved { actor, prop });
+    }
+}
@@ -1,16 +1,25 @@
 use std::ops::RangeInclusive;
 
-use avian3d::math::Scalar;
-use rand::Rng;
+use avian3d::math::{AdjustPrecision as _, Scalar, Vector};
+use bevy_ecs::entity::EntityHashMap;
+use bevy_time::Time;
+use rand::{Rng, RngCore};
 
-use crate::{prelude::*, rng::RngSource, verb::Throwing};
+use super::pull::find_prop_in_trace;
+use crate::{
+    interaction::ActorVelocity,
+    prelude::*,
+    rng::{ActorRng, RngSource},
+    verb::{Punting, Throwing},
+};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(PhysicsSchedule, throw.in_set(HandleVerbSystem::Throw));
+    app.add_systems(
+        PhysicsSchedule,
+        (throw, punt).chain().in_set(HandleVerbSystem::Throw),
+    );
 }
 
-/// Note: in constrast to the physcannon, we do not allow punting when not
-/// holding any prop. I think this should be handled by the user.
 fn throw(
     mut commands: Commands,
     mut q_actor: Query<(
@@ -20,6 +29,11 @@ fn throw(
         &mut AvianPickupActorState,
         &mut Cooldown,
         &Throwing,
+        &ActorVelocity,
+        Option<&mut ActorRng>,
+        Option<&PickupAimOrigin>,
+        Option<&AimRayOverride>,
+        Option<&PickupUpDirection>,
     )>,
     mut q_prop: Query<(
         &mut LinearVelocity,
@@ -27,18 +41,78 @@ fn throw(
         &ComputedMass,
         Option<&ThrownLinearSpeedOverride>,
         Option<&ThrownAngularSpeedOverride>,
+        Option<&ThrowSpinOverride>,
+        Option<&RigidBodyColliders>,
+        &Position,
+        &Rotation,
+        &ComputedCenterOfMass,
     )>,
+    q_parent: Query<&ChildOf>,
+    q_transform: Query<&GlobalTransform>,
+    q_collision_events_enabled: Query<(), With<CollisionEventsEnabled>>,
+    q_thrown: Query<&ThrownProp>,
     mut w_throw_event: MessageWriter<PropThrown>,
-    mut rng: ResMut<RngSource>,
+    mut global_rng: ResMut<RngSource>,
+    time: Res<Time>,
+    length_unit: Res<PhysicsLengthUnit>,
+    gravity: Res<Gravity>,
 ) {
-    for (actor, actor_transform, config, mut states, mut cooldown, throw) in q_actor.iter_mut() {
-        let actor_transform = actor_transform.compute_transform();
+    // Inheriting velocity reads the velocity of other rigid bodies, so it
+    // happens before any prop is thrown.
+    let mut q_body_velocity = q_prop.transmute_lens();
+    let q_body_velocity = q_body_velocity.query();
+    let inherited_velocities: EntityHashMap<_> = q_actor
+        .iter()
+        .filter(|(_, _, config, ..)| config.throw.velocity_inheritance != 0.0)
+        .map(|(actor, _, config, _, _, throw, actor_velocity, ..)| {
+            let velocity = actor_velocity_at(
+                throw.0,
+                actor,
+                actor_velocity,
+                config.throw.inherit_angular_velocity,
+                &q_parent,
+                &q_body_velocity,
+            );
+            (actor, velocity * config.throw.velocity_inheritance)
+        })
+        .collect();
+
+    for (
+        actor,
+        actor_transform,
+        config,
+        mut states,
+        mut cooldown,
+        throw,
+        _,
+        actor_rng,
+        aim_origin,
+        aim_ray,
+        up_direction,
+    ) in q_actor.iter_mut()
+    {
+        let up = PickupUpDirection::resolve(up_direction, config, &gravity);
+        let aim = PickupAimOrigin::resolve(aim_origin, aim_ray, actor_transform, up, &q_transform);
         let prop = throw.0;
         commands.entity(actor).remove::<Throwing>();
+        let inherited_velocity = inherited_velocities
+            .get(&actor)
+            .copied()
+            .unwrap_or(Vector::ZERO);
         // Safety: All props are rigid bodies, which are guaranteed to have a
         // `LinearVelocity`, `AngularVelocity`, and `Mass`.
-        let Ok((mut velocity, mut angvel, mass, lin_speed_override, ang_speed_override)) =
-            q_prop.get_mut(prop)
+        let Ok((
+            mut velocity,
+            mut angvel,
+            mass,
+            lin_speed_override,
+            ang_speed_override,
+            spin_override,
+            colliders,
+            _,
+            prop_rotation,
+            _,
+        )) = q_prop.get_mut(prop)
         else {
             error!("Prop entity was deleted or in an invalid state. Ignoring.");
             continue;
@@ -47,25 +121,238 @@ fn throw(
         // `prop_dist_sq > config.interaction_distance * config.interaction_distance`
         // but eh, that's fine. Better to respect players' input in such edge cases.
 
-        let lin_direction = actor_transform.forward();
+        let lin_direction = aim.forward().as_vec3().adjust_precision();
         let lin_speed = lin_speed_override
             .map(|s| s.0)
-            .unwrap_or_else(|| calculate_launch_speed(config, *mass));
-        velocity.0 = lin_direction * lin_speed;
+            .unwrap_or_else(|| calculate_launch_speed(config, *mass))
+            * length_unit.0;
+        velocity.0 = lin_direction * lin_speed + inherited_velocity;
 
-        let rand_direction = random_unit_vector(rng.as_mut());
-        let rand_magnitude = ang_speed_override.map(|s| s.0).unwrap_or_else(|| {
-            rng.as_mut()
-                .random_range(config.throw.angular_speed_range.clone())
-        });
-        angvel.0 = rand_direction * rand_magnitude;
+        let spin = spin_override.map_or(config.throw.spin, |s| s.0);
+        angvel.0 = match spin {
+            AvianPickupThrowSpin::Random => {
+                let mut rng: &mut dyn RngCore = match actor_rng {
+                    Some(actor_rng) => actor_rng.into_inner(),
+                    None => global_rng.as_mut(),
+                };
+                let rand_direction = random_unit_vector(&mut rng).adjust_precision();
+                let rand_magnitude = ang_speed_override
+                    .map(|s| s.0)
+                    .unwrap_or_else(|| rng.random_range(config.throw.angular_speed_range.clone()));
+                rand_direction * rand_magnitude
+            }
+            AvianPickupThrowSpin::ActorAxis { axis, speed } => {
+                aim.rotation.adjust_precision() * axis.normalize_or_zero() * speed
+            }
+            AvianPickupThrowSpin::PropAxis { axis, speed } => {
+                prop_rotation.0 * axis.normalize_or_zero() * speed
+            }
+            AvianPickupThrowSpin::Backspin(speed) => backspin_axis(lin_direction, up, &aim) * speed,
+            AvianPickupThrowSpin::Topspin(speed) => -backspin_axis(lin_direction, up, &aim) * speed,
+            AvianPickupThrowSpin::Preserve => angvel.0,
+        };
 
         *states = AvianPickupActorState::Idle;
+        commands.entity(prop).try_insert(LastHandledBy {
+            actor,
+            action: HandlingAction::Thrown,
+            at: time.elapsed_secs(),
+        });
+        if config.throw.track_impacts {
+            track_impacts(
+                &mut commands,
+                prop,
+                actor,
+                config,
+                velocity.0,
+                colliders,
+                time.elapsed_secs(),
+                &q_collision_events_enabled,
+                &q_thrown,
+            );
+        }
         w_throw_event.write(PropThrown { actor, prop });
         cooldown.throw();
     }
 }
 
+/// Inspired by [`CWeaponPhysCannon::PuntVPhysics`](https://github.com/ValveSoftware/source-sdk-2013/blob/master/src/game/server/hl2/weapon_physcannon.cpp)\
+/// The punted prop is launched along the aim, on top of whatever velocity it
+/// already had.
+fn punt(
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    mut q_actor: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &AvianPickupActor,
+            &mut Cooldown,
+            Option<&PickupAimOrigin>,
+            Option<&AimRayOverride>,
+            Option<&PickupUpDirection>,
+        ),
+        With<Punting>,
+    >,
+    mut q_prop: Query<(
+        &mut LinearVelocity,
+        &ComputedMass,
+        Option<&ThrownLinearSpeedOverride>,
+        Option<&RigidBodyColliders>,
+    )>,
+    q_puntable: Query<&RigidBody, (Without<HeldProp>, Without<SocketedProp>)>,
+    q_transform: Query<&GlobalTransform>,
+    q_collider_parent: Query<&ColliderOf>,
+    q_collision_events_enabled: Query<(), With<CollisionEventsEnabled>>,
+    q_thrown: Query<&ThrownProp>,
+    mut w_punt_event: MessageWriter<PropPunted>,
+    time: Res<Time>,
+    length_unit: Res<PhysicsLengthUnit>,
+    gravity: Res<Gravity>,
+) {
+    for (actor, actor_transform, config, mut cooldown, aim_origin, aim_ray, up_direction) in
+        q_actor.iter_mut()
+    {
+        commands.entity(actor).remove::<Punting>();
+        let up = PickupUpDirection::resolve(up_direction, config, &gravity);
+        let aim = PickupAimOrigin::resolve(aim_origin, aim_ray, actor_transform, up, &q_transform);
+        let prop = find_prop_in_trace(
+            &spatial_query,
+            aim,
+            config,
+            length_unit.0,
+            |entity| q_puntable.get(entity).is_ok_and(RigidBody::is_dynamic),
+            &q_collider_parent,
+        );
+        let Some(prop) = prop else {
+            continue;
+        };
+        let prop = prop.entity;
+        let Ok((mut velocity, mass, lin_speed_override, colliders)) = q_prop.get_mut(prop) else {
+            error!("Prop entity was deleted or in an invalid state. Ignoring.");
+            continue;
+        };
+        let lin_speed = lin_speed_override
+            .map(|s| s.0)
+            .unwrap_or_else(|| calculate_launch_speed(config, *mass))
+            * length_unit.0;
+        velocity.0 += aim.forward().as_vec3().adjust_precision() * lin_speed;
+
+        commands.entity(prop).try_insert(LastHandledBy {
+            actor,
+            action: HandlingAction::Punted,
+            at: time.elapsed_secs(),
+        });
+        if config.throw.track_impacts {
+            track_impacts(
+                &mut commands,
+                prop,
+                actor,
+                config,
+                velocity.0,
+                colliders,
+                time.elapsed_secs(),
+                &q_collision_events_enabled,
+                &q_thrown,
+            );
+        }
+        w_punt_event.write(PropPunted { actor, prop });
+        // Don't immediately pick up the prop we just punted.
+        cooldown.throw();
+    }
+}
+
+/// Inserts a [`ThrownProp`] on `prop` so that its impacts are reported.
+fn track_impacts(
+    commands: &mut Commands,
+    prop: Entity,
+    actor: Entity,
+    config: &AvianPickupActor,
+    velocity: Vector,
+    colliders: Option<&RigidBodyColliders>,
+    now: f32,
+    q_collision_events_enabled: &Query<(), With<CollisionEventsEnabled>>,
+    q_thrown: &Query<&ThrownProp>,
+) {
+    // Avian only sends collision events for colliders that opted in.
+    // Colliders we opted in on a previous throw that is still being
+    // tracked must be opted out again later as well.
+    let mut collision_events_enabled = q_thrown
+        .get(prop)
+        .map(|thrown| thrown.collision_events_enabled.clone())
+        .unwrap_or_default();
+    let newly_enabled: Vec<_> = colliders
+        .into_iter()
+        .flat_map(|colliders| colliders.iter())
+        .filter(|&collider| !q_collision_events_enabled.contains(collider))
+        .collect();
+    for &collider in &newly_enabled {
+        commands.entity(collider).try_insert(CollisionEventsEnabled);
+    }
+    collision_events_enabled.extend(newly_enabled);
+    commands.entity(prop).try_insert(ThrownProp {
+        thrower: actor,
+        at: now,
+        timeout: config.throw.impact_tracking_timeout,
+        previous_velocity: velocity,
+        collision_events_enabled,
+    });
+}
+
+/// The velocity the prop would have if it was rigidly attached to the actor.
+/// Prefers the velocity of the rigid body the actor belongs to over the
+/// estimated velocity of the actor itself.
+fn actor_velocity_at(
+    prop: Entity,
+    actor: Entity,
+    estimate: &ActorVelocity,
+    include_angular: bool,
+    q_parent: &Query<&ChildOf>,
+    q_body_velocity: &Query<(
+        &LinearVelocity,
+        &AngularVelocity,
+        &Position,
+        &Rotation,
+        &ComputedCenterOfMass,
+    )>,
+) -> Vector {
+    // Rigid bodies rotate around their center of mass.
+    let body_velocity = |entity: Entity| {
+        let (linear, angular, position, rotation, center_of_mass) =
+            q_body_velocity.get(entity).ok()?;
+        Some((
+            linear.0,
+            angular.0,
+            position.0 + rotation.0 * center_of_mass.0,
+        ))
+    };
+    let Some((.., point)) = body_velocity(prop) else {
+        return Vector::ZERO;
+    };
+    let body = std::iter::once(actor)
+        .chain(q_parent.iter_ancestors(actor))
+        .find_map(body_velocity);
+    let (linear, angular, center) = body.unwrap_or((
+        estimate.linear,
+        estimate.angular,
+        estimate.translation.unwrap_or(point),
+    ));
+    if include_angular {
+        linear + angular.cross(point - center)
+    } else {
+        linear
+    }
+}
+
+/// The axis that makes a prop thrown in `direction` spin backwards, i.e. its
+/// top, as seen from the actor's `up` direction, moves against the throw direction.
+fn backspin_axis(direction: Vector, up: Dir3, actor_transform: &Transform) -> Vector {
+    direction
+        .cross(up.as_vec3().adjust_precision())
+        .try_normalize()
+        .unwrap_or_else(|| actor_transform.right().as_vec3().adjust_precision())
+}
+
 fn random_unit_vector(rng: &mut impl Rng) -> Vec3 {
     Sphere::new(1.0).sample_boundary(rng)
 }
@@ -108,7 +395,7 @@ fn remap_through_spline(
 /// Hermite basis function for smooth interpolation
 /// Assumes `value` is between 0 and 1 inclusive.
 /// Corresponds to 2013's `SimpleSpline`
-fn simple_spline(value: f32) -> f32 {
+fn simple_spline(value: Scalar) -> Scalar {
     let value_2 = value * value;
     let value_3 = value_2 * value;
     -2.0 * value_3 + 3.0 * value_2
@@ -121,12 +408,12 @@ mod test {
     use super::*;
 
     // USES INCHES!
-    const MINFORCE: f32 = 700.0;
-    const MAXFORCE: f32 = 1500.0;
+    const MINFORCE: Scalar = 700.0;
+    const MAXFORCE: Scalar = 1500.0;
 
     #[test]
     fn test_remap_through_spline() {
-        let remap = |val: f32| remap_through_spline(val, 100.0..=600., MAXFORCE..=MINFORCE);
+        let remap = |val: Scalar| remap_through_spline(val, 100.0..=600., MAXFORCE..=MINFORCE);
         // The speed we can muster is lower the heavier the object is.
         assert_eq!(remap(100.), MAXFORCE);
         assert_eq!(remap(600.), MINFORCE);
@@ -8,12 +8,18 @@ use bevy_ecs::prelude::*;
 
 pub mod actor;
 mod cooldown;
+#[cfg(feature = "debug")]
+pub mod debug;
 pub mod input;
 mod interaction;
 mod math;
 pub mod output;
 pub mod prop;
 mod rng;
+pub mod socket;
+pub mod state;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod verb;
 pub use verb::Holding;
 
@@ -25,9 +31,11 @@ pub mod prelude {
         cooldown::prelude::*,
         prop::{NonPickupMass, prelude::*},
     };
+    #[cfg(feature = "debug")]
+    pub use crate::debug::prelude::*;
     pub use crate::{
         AvianPickupPlugin, AvianPickupSystem, actor::prelude::*, input::prelude::*,
-        output::prelude::*,
+        output::prelude::*, socket::prelude::*,
     };
     pub(crate) use avian3d::prelude::*;
     pub(crate) use bevy_app::prelude::*;
@@ -75,6 +83,7 @@ impl Plugin for AvianPickupPlugin {
             (
                 AvianPickupSystem::First,
                 AvianPickupSystem::HandleVerb,
+                AvianPickupSystem::HandleSockets,
                 AvianPickupSystem::ResetIdle,
                 AvianPickupSystem::TickTimers,
                 AvianPickupSystem::Last,
@@ -89,6 +98,11 @@ impl Plugin for AvianPickupPlugin {
                 HandleVerbSystem::Hold,
                 HandleVerbSystem::Drop,
                 HandleVerbSystem::Throw,
+                HandleVerbSystem::Freeze,
+                HandleVerbSystem::Unfreeze,
+                HandleVerbSystem::Place,
+                HandleVerbSystem::Stash,
+                HandleVerbSystem::Retrieve,
             )
                 .chain()
                 .in_set(AvianPickupSystem::HandleVerb),
@@ -103,6 +117,8 @@ impl Plugin for AvianPickupPlugin {
             prop::plugin,
             verb::plugin,
             rng::plugin,
+            socket::plugin,
+            state::plugin,
         ));
     }
 }
@@ -112,13 +128,19 @@ impl Plugin for AvianPickupPlugin {
 /// This is run in Avian's [`PhysicsStepSet::First`] and scheduled under [`PhysicsSchedule`].
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum AvianPickupSystem {
-    /// Runs at the start of the [`AvianPickupSystem`]. Empty by default.
+    /// Runs at the start of the [`AvianPickupSystem`].
+    /// Turns [`AvianPickupInput`](crate::prelude::AvianPickupInput)s into
+    /// verbs, tracks impacts of thrown props and estimates the velocity of
+    /// actors.
     First,
     /// Adds forces to an object held by
     /// [`AvianPickupActorState::Holding`](crate::prelude::AvianPickupActorState::Holding)
     /// in order to keep it in place in front of the
     /// [`AvianPickupActor`](crate::prelude::AvianPickupActor).
     HandleVerb,
+    /// Captures props in
+    /// [`PropSocket`](crate::prelude::PropSocket)s and releases them again.
+    HandleSockets,
     /// Resets the
     /// [`AvianPickupActorState`](crate::prelude::AvianPickupActorState) to
     /// [`AvianPickupActorState::Idle`](crate::prelude::AvianPickupActorState::Idle)
@@ -126,7 +148,8 @@ pub enum AvianPickupSystem {
     ResetIdle,
     /// Advances internal cooldown timers.
     TickTimers,
-    /// Runs at the end of the [`AvianPickupSystem`]. Empty by default.
+    /// Runs at the end of the [`AvianPickupSystem`].
+    /// Advances the [`AvianPickupTick`](crate::state::AvianPickupTick).
     Last,
 }
 
@@ -136,4 +159,9 @@ pub(crate) enum HandleVerbSystem {
     Hold,
     Drop,
     Throw,
+    Freeze,
+    Unfreeze,
+    Place,
+    Stash,
+    Retrieve,
 }
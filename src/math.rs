@@ -1,7 +1,32 @@
 use crate::prelude::*;
-use avian3d::prelude::*;
+use avian3d::{
+    math::{AdjustPrecision as _, Matrix3, Quaternion, Scalar, Vector},
+    prelude::*,
+};
 
-pub(crate) const METERS_PER_INCH: f32 = 0.0254;
+pub(crate) const METERS_PER_INCH: Scalar = 0.0254;
+
+/// Converts a [`Scalar`] to `f32` for things that are single precision no
+/// matter what precision Avian uses, e.g. gizmos and masses.\
+/// The scalar counterpart of [`AsF32`](avian3d::math::AsF32).
+pub(crate) trait ScalarAsF32 {
+    fn f32(self) -> f32;
+}
+
+impl ScalarAsF32 for f32 {
+    fn f32(self) -> f32 {
+        self
+    }
+}
+
+impl ScalarAsF32 for f64 {
+    fn f32(self) -> f32 {
+        self as f32
+    }
+}
+
+/// Combines all colliders of a rigid body that pass the `filter` into a single
+/// compound collider in the local space of the rigid body.
 pub(crate) fn rigid_body_compound_collider(
     rigid_body_transform: &Transform,
     collider_entities: impl IntoIterator<Item = Entity>,
@@ -14,8 +39,11 @@ pub(crate) fn rigid_body_compound_collider(
         let transform = transform.compute_transform();
         let layers = layers.copied().unwrap_or_default();
         if filter.test(entity, layers) {
-            let relative_translation = transform.translation - rigid_body_transform.translation;
-            let relative_rotation = transform.rotation * rigid_body_transform.rotation.inverse();
+            let inverse_rotation = rigid_body_transform.rotation.adjust_precision().inverse();
+            let relative_translation = inverse_rotation
+                * (transform.translation.adjust_precision()
+                    - rigid_body_transform.translation.adjust_precision());
+            let relative_rotation = inverse_rotation * transform.rotation.adjust_precision();
             if let Some(compound) = collider.shape_scaled().as_compound() {
                 // Need to unpack compound shapes because we are later returning a big compound collider for the whole rigid body
                 // and parry crashes on nested compound shapes
@@ -36,3 +64,50 @@ pub(crate) fn rigid_body_compound_collider(
 
     (!colliders.is_empty()).then(|| Collider::compound(colliders))
 }
+
+/// The rotation that turns the world's +Y axis into `up` and its -Z axis into
+/// the actor's heading, i.e. the direction it faces along the plane
+/// perpendicular to `up`.
+/// Yaw, pitch and roll relative to `up` are those of a rotation in this frame.
+pub(crate) fn up_frame(up: Dir3, actor_rotation: Quaternion) -> Quaternion {
+    let up = up.as_vec3().adjust_precision();
+    let along_plane = |direction: Vector| {
+        let direction = direction.reject_from_normalized(up);
+        (direction.length_squared() > 1e-6).then(|| direction.normalize())
+    };
+    let forward = actor_rotation * Vector::NEG_Z;
+    // Looking straight down, the top of the actor's head points where it is
+    // heading. Looking straight up, it points the other way.
+    let head = actor_rotation * Vector::Y * -forward.dot(up).signum();
+    let heading = along_plane(forward)
+        .or_else(|| along_plane(head))
+        .unwrap_or_else(|| up.any_orthonormal_vector());
+    let back = -heading;
+    Quaternion::from_mat3(&Matrix3::from_cols(up.cross(back), up, back))
+}
+
+#[cfg(test)]
+mod test {
+    use avian3d::math::{FRAC_PI_2, PI};
+
+    use super::*;
+
+    #[test]
+    fn test_up_frame_follows_heading() {
+        // Standing upside down while facing +X.
+        let actor_rotation =
+            Quaternion::from_rotation_z(PI) * Quaternion::from_rotation_y(FRAC_PI_2);
+        let frame = up_frame(Dir3::NEG_Y, actor_rotation);
+        assert!((frame * Vector::Y).abs_diff_eq(Vector::NEG_Y, 1e-5));
+        assert!((frame * Vector::NEG_Z).abs_diff_eq(actor_rotation * Vector::NEG_Z, 1e-5));
+    }
+
+    #[test]
+    fn test_up_frame_looking_straight_down() {
+        // Facing +X, then looking down.
+        let actor_rotation =
+            Quaternion::from_rotation_y(-FRAC_PI_2) * Quaternion::from_rotation_x(-FRAC_PI_2);
+        let frame = up_frame(Dir3::Y, actor_rotation);
+        assert!((frame * Vector::NEG_Z).abs_diff_eq(Vector::X, 1e-5));
+    }
+}
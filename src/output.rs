@@ -3,14 +3,30 @@
 //! prop-related events. Handle these to e.g. play sound effects or show
 //! visual effects.
 
+use avian3d::math::{Scalar, Vector};
+
 use crate::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_message::<PropThrown>().add_message::<PropDropped>();
+    app.add_message::<PropThrown>()
+        .add_message::<PropPunted>()
+        .add_message::<PropDropped>()
+        .add_message::<PropFrozen>()
+        .add_message::<PropUnfrozen>()
+        .add_message::<PropPlaced>()
+        .add_message::<PropPlacementFailed>()
+        .add_message::<PropSocketed>()
+        .add_message::<PropUnsocketed>()
+        .add_message::<PropStashed>()
+        .add_message::<PropRetrieved>()
+        .add_message::<ThrownPropImpact>();
 }
 
 pub(super) mod prelude {
-    pub use super::{PropDropped, PropThrown};
+    pub use super::{
+        PropDropped, PropFrozen, PropPlaced, PropPlacementFailed, PropPunted, PropRetrieved,
+        PropSocketed, PropStashed, PropThrown, PropUnfrozen, PropUnsocketed, ThrownPropImpact,
+    };
 }
 
 /// Message sent when a prop is thrown by an actor.
@@ -30,6 +46,23 @@ pub struct PropThrown {
     pub actor: Entity,
 }
 
+/// Message sent when a prop is punted by an actor.
+/// This is meant for the user to listen to in order to play sound effects, etc.
+/// Sending this has no effect on the prop itself.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PropPunted {
+    /// The punted prop.
+    pub prop: Entity,
+    /// The actor that punted the prop.
+    pub actor: Entity,
+}
+
 /// Message sent when a prop is dropped by an actor.
 /// This is meant for the user to listen to in order to play sound effects, etc.
 /// Sending this has no effect on the prop itself.
@@ -50,3 +83,184 @@ pub struct PropDropped {
     /// volition.
     pub forced: bool,
 }
+
+/// Message sent when a prop is frozen in place by an actor.
+/// This is meant for the user to listen to in order to play sound effects, etc.
+/// Sending this has no effect on the prop itself.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PropFrozen {
+    /// The frozen prop.
+    pub prop: Entity,
+    /// The actor that froze the prop.
+    pub actor: Entity,
+}
+
+/// Message sent when a frozen prop is unfrozen by an actor.
+/// This is meant for the user to listen to in order to play sound effects, etc.
+/// Sending this has no effect on the prop itself.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PropUnfrozen {
+    /// The unfrozen prop.
+    pub prop: Entity,
+    /// The actor that unfroze the prop.
+    pub actor: Entity,
+}
+
+/// Message sent when a prop is placed by an actor.
+/// This is meant for the user to listen to in order to play sound effects, etc.
+/// Sending this has no effect on the prop itself.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PropPlaced {
+    /// The placed prop.
+    pub prop: Entity,
+    /// The actor that placed the prop.
+    pub actor: Entity,
+}
+
+/// Message sent when an actor tried to place a prop, but could not.
+/// The actor keeps holding the prop in that case.
+/// This is meant for the user to listen to in order to play sound effects, etc.
+/// Sending this has no effect on the prop itself.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PropPlacementFailed {
+    /// The prop that could not be placed.
+    pub prop: Entity,
+    /// The actor that tried to place the prop.
+    pub actor: Entity,
+    /// Why the prop could not be placed.
+    pub reason: PlacementError,
+}
+
+/// Message sent when a prop is captured by a
+/// [`PropSocket`](crate::prelude::PropSocket).
+/// This is meant for the user to listen to in order to play sound effects,
+/// power up the charger the battery was put in, etc.
+/// Sending this has no effect on the prop itself.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PropSocketed {
+    /// The captured prop.
+    pub prop: Entity,
+    /// The socket that captured the prop.
+    pub socket: Entity,
+}
+
+/// Message sent when a prop leaves its [`PropSocket`](crate::prelude::PropSocket),
+/// either by being pulled out by an actor or by being knocked out of an
+/// unlocked socket.
+/// This is meant for the user to listen to in order to play sound effects, etc.
+/// Sending this has no effect on the prop itself.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PropUnsocketed {
+    /// The prop that left the socket.
+    pub prop: Entity,
+    /// The socket the prop was in.
+    pub socket: Entity,
+    /// The actor that pulled the prop out of the socket, if any.
+    pub actor: Option<Entity>,
+}
+
+/// Message sent when a prop is stashed into the
+/// [`PropInventory`](crate::prelude::PropInventory) of an actor.
+/// This is meant for the user to listen to in order to play sound effects, etc.
+/// Sending this has no effect on the prop itself.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PropStashed {
+    /// The stashed prop.
+    pub prop: Entity,
+    /// The actor that stashed the prop.
+    pub actor: Entity,
+}
+
+/// Message sent when a prop is retrieved from the
+/// [`PropInventory`](crate::prelude::PropInventory) of an actor.
+/// The actor is holding the prop afterwards.
+/// This is meant for the user to listen to in order to play sound effects, etc.
+/// Sending this has no effect on the prop itself.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PropRetrieved {
+    /// The retrieved prop.
+    pub prop: Entity,
+    /// The actor that retrieved the prop.
+    pub actor: Entity,
+}
+
+/// Message sent when a prop marked with a [`ThrownProp`](crate::prelude::ThrownProp)
+/// starts touching something during its flight.
+/// This is meant for the user to listen to in order to deal damage, play sound
+/// effects, etc.
+/// Sending this has no effect on the prop itself.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ThrownPropImpact {
+    /// The thrown prop.
+    pub prop: Entity,
+    /// The actor that threw the prop.
+    pub thrower: Entity,
+    /// The entity that was hit. This is the rigid body if the hit collider
+    /// belongs to one, and the collider otherwise.
+    pub other: Entity,
+    /// The speed in m/s at which the prop hit the other entity, measured
+    /// along the contact normal.
+    pub impact_speed: Scalar,
+    /// The kinetic energy in J of the prop corresponding to
+    /// [`ThrownPropImpact::impact_speed`].
+    pub kinetic_energy: Scalar,
+    /// The world-space point of contact.
+    pub point: Vector,
+    /// The world-space contact normal, pointing from the other entity towards
+    /// the prop.
+    pub normal: Vector,
+}
@@ -3,17 +3,39 @@
 use std::ops::RangeInclusive;
 
 use crate::prelude::*;
-use avian3d::{math::Scalar, prelude::Mass};
+use avian3d::{
+    math::{Quaternion, Scalar, Vector},
+    prelude::Mass,
+};
 use bevy_ecs::prelude::*;
 
-use crate::prelude::AvianPickupActor;
+use crate::prelude::{AvianPickupActor, AvianPickupRotationSnapping, AvianPickupThrowSpin};
 
-pub(super) fn plugin(_app: &mut App) {}
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PreferredPickupRotation>()
+        .register_type::<PrePickupRotation>()
+        .register_type::<PrePickupMass>()
+        .register_type::<PitchRangeOverride>()
+        .register_type::<PreferredPickupDistanceOverride>()
+        .register_type::<PickupMassOverride>()
+        .register_type::<ThrownLinearSpeedOverride>()
+        .register_type::<ThrownAngularSpeedOverride>()
+        .register_type::<ThrowSpinOverride>()
+        .register_type::<RotationSnappingOverride>()
+        .register_type::<NonPickupMass>()
+        .register_type::<HeldProp>()
+        .register_type::<FrozenProp>()
+        .register_type::<StashedProp>()
+        .register_type::<LastHandledBy>()
+        .register_type::<ThrownProp>();
+}
 
 pub(super) mod prelude {
     pub use super::{
-        HeldProp, PickupMassOverride, PitchRangeOverride, PreferredPickupDistanceOverride,
-        PreferredPickupRotation, ThrownAngularSpeedOverride, ThrownLinearSpeedOverride,
+        FrozenProp, HandlingAction, HeldProp, LastHandledBy, PickupMassOverride,
+        PitchRangeOverride, PreferredPickupDistanceOverride, PreferredPickupRotation,
+        RotationSnappingOverride, StashedProp, ThrowSpinOverride, ThrownAngularSpeedOverride,
+        ThrownLinearSpeedOverride, ThrownProp,
     };
 }
 
@@ -32,10 +54,31 @@ pub(super) mod prelude {
     derive(serde::Serialize, serde::Deserialize),
     reflect(Serialize, Deserialize)
 )]
-pub struct PreferredPickupRotation(pub Quat);
+pub struct PreferredPickupRotation(pub Quaternion);
+
+/// The rotation a prop had in the actor's local space when it was picked up.
+/// The prop keeps this rotation while held, unless it has a
+/// [`PreferredPickupRotation`]. Inserted automatically.
+#[derive(Debug, Clone, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PrePickupRotation(pub Quaternion);
 
-#[derive(Debug, Clone, PartialEq, Component)]
-pub(crate) struct PrePickupRotation(pub Quat);
+/// The mass an object had right before it was picked up, no matter whether
+/// it came from a [`Mass`] or was computed from its colliders.
+/// Inserted automatically.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PrePickupMass(pub Scalar);
 
 /// Insert this on a prop to override
 /// [`AvianPickupActorHoldConfig::pitch_range`](crate::prelude::AvianPickupActorHoldConfig::pitch_range).
@@ -121,12 +164,50 @@ impl Default for ThrownAngularSpeedOverride {
     }
 }
 
+/// Insert this on a prop to override
+/// [`AvianPickupActorThrowConfig::spin`](crate::prelude::AvianPickupActorThrowConfig::spin).
+#[derive(Debug, Clone, Copy, PartialEq, Component, Default, Reflect)]
+#[reflect(Debug, Component, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ThrowSpinOverride(pub AvianPickupThrowSpin);
+
+/// Insert this on a prop to override
+/// [`AvianPickupActorHoldConfig::rotation_snapping`](crate::prelude::AvianPickupActorHoldConfig::rotation_snapping).
+/// Use `RotationSnappingOverride(None)` to disable snapping for a prop.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct RotationSnappingOverride(pub Option<AvianPickupRotationSnapping>);
+
+impl Default for RotationSnappingOverride {
+    /// Enables snapping with the default configuration, as this is the
+    /// most common reason to add this component.
+    fn default() -> Self {
+        Self(Some(default()))
+    }
+}
+
 /// The cached mass that an object had before it was picked up
 /// that will be restored again when it is dropped.
 /// In other words, this is the mass before and after the pickup.
 /// Only used if the object had a [`Mass`] component.
-#[derive(Debug, Clone, Copy, PartialEq, Component)]
-pub(crate) struct NonPickupMass(pub Mass);
+/// Inserted automatically.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct NonPickupMass(pub Mass);
 
 /// Marker component for props that are held by an [`AvianPickupActor`].
 #[derive(Debug, Clone, Copy, PartialEq, Component, Hash, Default, Reflect)]
@@ -137,3 +218,118 @@ pub(crate) struct NonPickupMass(pub Mass);
     reflect(Serialize, Deserialize)
 )]
 pub struct HeldProp;
+
+/// Component for props that were frozen in place by an [`AvianPickupActor`]
+/// through [`AvianPickupAction::Freeze`](crate::prelude::AvianPickupAction::Freeze).
+/// Frozen props are turned into a [`RigidBody::Static`] until they are
+/// unfrozen again through
+/// [`AvianPickupAction::Unfreeze`](crate::prelude::AvianPickupAction::Unfreeze).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, Hash, Reflect)]
+#[reflect(Debug, Component, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct FrozenProp {
+    /// The actor that froze the prop.
+    #[entities]
+    pub by: Entity,
+}
+
+/// Component for props that were stashed into the [`PropInventory`](crate::prelude::PropInventory)
+/// of an [`AvianPickupActor`] through
+/// [`AvianPickupAction::Stash`](crate::prelude::AvianPickupAction::Stash).
+/// Removed again when the prop is retrieved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, Hash, Reflect)]
+#[reflect(Debug, Component, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct StashedProp {
+    /// The actor whose inventory the prop is in.
+    #[entities]
+    pub by: Entity,
+}
+
+/// Component inserted on props whenever an [`AvianPickupActor`] releases them,
+/// i.e. throws, drops, freezes, places or stashes them, or punts them.
+/// Useful for attributing damage done by the prop, e.g.
+/// "the player killed the zombie with a thrown saw blade".\
+/// Removed again according to the actor's
+/// [`AvianPickupActorAttributionConfig`](crate::prelude::AvianPickupActorAttributionConfig).
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct LastHandledBy {
+    /// The actor that last released the prop.
+    #[entities]
+    pub actor: Entity,
+    /// How the actor released the prop.
+    pub action: HandlingAction,
+    /// The elapsed [`Time`](bevy_time::Time) in seconds at which the prop was released.
+    pub at: f32,
+}
+
+/// How an actor released a prop. Used in [`LastHandledBy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum HandlingAction {
+    /// The prop was thrown.
+    Thrown,
+    /// The prop was punted without being picked up.
+    Punted,
+    /// The prop was dropped, either on purpose or because it got stuck.
+    Dropped,
+    /// The prop was frozen in place.
+    Frozen,
+    /// The prop was placed on a surface.
+    Placed,
+    /// The prop was stashed into the actor's
+    /// [`PropInventory`](crate::prelude::PropInventory).
+    Stashed,
+}
+
+/// Component inserted on thrown and punted props while their impacts are being tracked.
+/// Only inserted if
+/// [`AvianPickupActorThrowConfig::track_impacts`](crate::prelude::AvianPickupActorThrowConfig::track_impacts)
+/// is enabled.\
+/// While present, a [`ThrownPropImpact`](crate::prelude::ThrownPropImpact)
+/// is sent every time the prop starts touching something.
+/// Removed again once the prop comes to rest, is picked up again, or
+/// [`AvianPickupActorThrowConfig::impact_tracking_timeout`](crate::prelude::AvianPickupActorThrowConfig::impact_tracking_timeout)
+/// has elapsed.
+#[derive(Debug, Clone, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ThrownProp {
+    /// The actor that threw the prop.
+    #[entities]
+    pub thrower: Entity,
+    /// The elapsed [`Time`](bevy_time::Time) in seconds at which the prop was thrown.
+    pub at: f32,
+    /// How many seconds after being thrown the prop stops being tracked.
+    pub timeout: f32,
+    /// The prop's velocity before the last physics step, i.e. before any
+    /// impact changed it.
+    pub(crate) previous_velocity: Vector,
+    /// The colliders that got [`CollisionEventsEnabled`] inserted for tracking
+    /// and need it removed again afterwards.
+    #[entities]
+    pub(crate) collision_events_enabled: Vec<Entity>,
+}
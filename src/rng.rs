@@ -1,12 +1,16 @@
 use crate::prelude::*;
-use rand::RngCore;
+use rand::{RngCore, SeedableRng as _};
+use rand_pcg::Pcg32;
 
 pub(super) fn plugin(app: &mut App) {
-    app.init_resource::<RngSource>();
+    app.init_resource::<RngSource>().register_type::<ActorRng>();
 }
 
 /// A resource that provides a source of randomness.
-/// Will fall back to [`rand::rng()`] if no source is provided.
+/// Will fall back to [`rand::rng()`] if no source is provided.\
+/// Shared by all actors, so the randomness an actor gets depends on what
+/// every other actor did before. Add an [`ActorRng`] to an actor to give it
+/// its own source of randomness instead.
 #[derive(Resource, Default)]
 pub struct RngSource(pub Option<Box<dyn RngCore + Send + Sync>>);
 
@@ -33,3 +37,65 @@ impl RngSource {
         }
     }
 }
+
+/// Component for an [`AvianPickupActor`] that gives it its own source of
+/// randomness, e.g. for the spin of thrown props. Takes precedence over the
+/// global [`RngSource`].\
+/// Cloning it captures its state, so it can be saved, sent over the
+/// network, and restored to replay throws exactly.
+/// It is also part of the [`AvianPickupSnapshot`](crate::state::AvianPickupSnapshot).
+///
+/// This wraps a [`Pcg32`], which is fast, produces the same numbers on every
+/// platform, and is good enough for gameplay, but not suitable for
+/// cryptography.
+#[derive(Debug, Clone, PartialEq, Eq, Component, Reflect)]
+#[reflect(opaque)]
+#[reflect(Debug, Component, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ActorRng(pub Pcg32);
+
+impl ActorRng {
+    /// Creates a new generator from a seed.
+    /// Generators with the same seed produce the same numbers.
+    pub fn from_seed(seed: u64) -> Self {
+        Self(Pcg32::seed_from_u64(seed))
+    }
+}
+
+impl RngCore for ActorRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn actor_rng_is_reproducible() {
+        let mut rng = ActorRng::from_seed(42);
+        rng.next_u64();
+        // Copying the state, like a rollback would, replays the same numbers.
+        let mut restored = rng.clone();
+        let numbers: Vec<_> = (0..10).map(|_| rng.next_u64()).collect();
+        let replayed: Vec<_> = (0..10).map(|_| restored.next_u64()).collect();
+        assert_eq!(numbers, replayed);
+        assert_ne!(
+            ActorRng::from_seed(42).next_u64(),
+            ActorRng::from_seed(43).next_u64()
+        );
+    }
+}
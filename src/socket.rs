@@ -0,0 +1,323 @@
+//! Module for sockets that capture props, e.g. for puzzles like
+//! "put the battery into the charger".
+
+use avian3d::math::{AdjustPrecision as _, Scalar, Vector};
+use bevy_platform::collections::HashSet;
+use bevy_time::Time;
+
+use crate::{
+    prelude::*,
+    verb::{Holding, SetVerb, Verb},
+};
+
+pub(super) mod prelude {
+    pub use super::{PropSocket, SocketedProp};
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PropSocket>()
+        .register_type::<SocketedProp>()
+        .register_type::<ReleasedProp>()
+        .register_type::<LeftSocket>()
+        .add_systems(
+            PhysicsSchedule,
+            (mark_released_props, release_displaced_props, capture_props)
+                .chain()
+                .in_set(AvianPickupSystem::HandleSockets),
+        );
+}
+
+/// Component for an entity that captures props brought close to it and snaps
+/// them into place. A socket holds at most one prop at a time.\
+/// Props are captured while being held by an [`AvianPickupActor`], in which
+/// case the actor drops them, or shortly after being dropped or thrown.
+/// Props that are simply lying around next to the socket are not captured.
+///
+/// Sends [`PropSocketed`](crate::prelude::PropSocketed) when a prop is
+/// captured and [`PropUnsocketed`](crate::prelude::PropUnsocketed) when it
+/// leaves the socket again.
+///
+/// Requires the entity to also hold a [`Transform`] and [`GlobalTransform`].
+#[derive(Debug, Component, Clone, PartialEq, Reflect)]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PropSocket {
+    /// The spatial query filter that props must pass in order to be captured.
+    /// It is tested against the prop's rigid body entity and its
+    /// [`CollisionLayers`].\
+    /// Default: Include all entities
+    pub prop_filter: SpatialQueryFilter,
+    /// The transform of a captured prop relative to the socket.\
+    /// Default: [`Transform::IDENTITY`]
+    pub snap_transform: Transform,
+    /// How close the prop's origin must be to the snap position in order to be
+    /// captured. Unlocked props that are knocked further away than this
+    /// are released from the socket.\
+    /// Default: 0.3 m
+    pub capture_radius: Scalar,
+    /// For how many seconds after being dropped or thrown a prop can still be
+    /// captured.\
+    /// Default: 1.0 s
+    pub release_window: f32,
+    /// Whether captured props are turned into a [`RigidBody::Static`] so that
+    /// they stay in place no matter what hits them.
+    /// They are turned back into a [`RigidBody::Dynamic`] when they leave the
+    /// socket.\
+    /// Default: `true`
+    pub lock: bool,
+    /// Whether actors can pull captured props back out of the socket.
+    /// If `false`, captured props cannot be picked up at all.\
+    /// Default: `true`
+    pub removable: bool,
+}
+
+impl Default for PropSocket {
+    fn default() -> Self {
+        Self {
+            prop_filter: default(),
+            snap_transform: Transform::IDENTITY,
+            capture_radius: 0.3,
+            release_window: 1.0,
+            lock: true,
+            removable: true,
+        }
+    }
+}
+
+/// Component inserted on props that were captured by a [`PropSocket`].
+/// Removed again when the prop leaves the socket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, Hash, Reflect)]
+#[reflect(Debug, Component, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SocketedProp {
+    /// The socket that captured the prop.
+    #[entities]
+    pub socket: Entity,
+    /// Whether the socket turned the prop into a [`RigidBody::Static`].
+    pub locked: bool,
+}
+
+/// When a prop was last dropped or thrown, in seconds since startup.
+/// Used to decide whether a [`PropSocket`] may still capture it.
+/// Inserted automatically.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ReleasedProp {
+    at: f32,
+}
+
+/// Inserted on props that just left a socket so that they are not captured
+/// again right away. Removed once the prop is out of the socket's range.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct LeftSocket(#[entities] Entity);
+
+fn mark_released_props(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut r_dropped: MessageReader<PropDropped>,
+    mut r_thrown: MessageReader<PropThrown>,
+) {
+    let at = time.elapsed_secs();
+    let props = r_dropped
+        .read()
+        .map(|event| event.prop)
+        .chain(r_thrown.read().map(|event| event.prop));
+    for prop in props {
+        commands.entity(prop).try_insert(ReleasedProp { at });
+    }
+}
+
+/// Releases socketed props that were knocked out of their socket or whose
+/// socket no longer exists, and forgets about sockets that props have left.
+fn release_displaced_props(
+    mut commands: Commands,
+    q_socket: Query<(&GlobalTransform, &PropSocket)>,
+    q_socketed: Query<(Entity, &Position, &SocketedProp)>,
+    q_left: Query<(Entity, &Position, &LeftSocket)>,
+    length_unit: Res<PhysicsLengthUnit>,
+    mut w_unsocketed: MessageWriter<PropUnsocketed>,
+) {
+    let is_in_range = |socket: Entity, position: Vector| {
+        q_socket.get(socket).is_ok_and(|(transform, config)| {
+            let snap = transform.compute_transform() * config.snap_transform;
+            let capture_radius = config.capture_radius * length_unit.0;
+            snap.translation
+                .adjust_precision()
+                .distance_squared(position)
+                <= capture_radius * capture_radius
+        })
+    };
+    for (prop, position, &socketed) in q_socketed.iter() {
+        let socket_exists = q_socket.contains(socketed.socket);
+        if socket_exists && (socketed.locked || is_in_range(socketed.socket, position.0)) {
+            continue;
+        }
+        unsocket(&mut commands, prop, socketed, None, &mut w_unsocketed);
+    }
+    for (prop, position, left) in q_left.iter() {
+        if !is_in_range(left.0, position.0) {
+            commands.entity(prop).remove::<LeftSocket>();
+        }
+    }
+}
+
+fn capture_props(
+    mut commands: Commands,
+    time: Res<Time>,
+    q_socket: Query<(Entity, &GlobalTransform, &PropSocket)>,
+    q_socketed: Query<&SocketedProp>,
+    q_actor: Query<(Entity, &Holding)>,
+    mut q_prop: Query<
+        (
+            Entity,
+            &mut Position,
+            &mut Rotation,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+            Option<&CollisionLayers>,
+            Has<HeldProp>,
+            Option<&ReleasedProp>,
+            Option<&LeftSocket>,
+        ),
+        (
+            Or<(With<HeldProp>, With<ReleasedProp>)>,
+            Without<SocketedProp>,
+            Without<FrozenProp>,
+        ),
+    >,
+    length_unit: Res<PhysicsLengthUnit>,
+    mut w_socketed: MessageWriter<PropSocketed>,
+) {
+    let now = time.elapsed_secs();
+    let mut occupied_sockets: HashSet<_> =
+        q_socketed.iter().map(|socketed| socketed.socket).collect();
+    let mut captured_props = HashSet::new();
+    for (socket, socket_transform, config) in q_socket.iter() {
+        if occupied_sockets.contains(&socket) {
+            continue;
+        }
+        let snap = socket_transform.compute_transform() * config.snap_transform;
+        let capture_radius = config.capture_radius * length_unit.0;
+        for (
+            prop,
+            mut position,
+            mut rotation,
+            mut velocity,
+            mut angvel,
+            layers,
+            is_held,
+            released,
+            left,
+        ) in q_prop.iter_mut()
+        {
+            if captured_props.contains(&prop) || left.is_some_and(|left| left.0 == socket) {
+                continue;
+            }
+            if position.distance_squared(snap.translation.adjust_precision())
+                > capture_radius * capture_radius
+            {
+                continue;
+            }
+            if !config
+                .prop_filter
+                .test(prop, layers.copied().unwrap_or_default())
+            {
+                continue;
+            }
+            if is_held {
+                // Let the actor drop the prop first so that its mass is restored.
+                // It is captured as soon as the drop is done.
+                if let Some((actor, _)) = q_actor.iter().find(|(_, holding)| holding.0 == prop) {
+                    commands.entity(actor).queue(SetVerb::new(Verb::Drop {
+                        prop,
+                        forced: false,
+                    }));
+                }
+                continue;
+            }
+            if released.is_none_or(|released| now - released.at > config.release_window) {
+                continue;
+            }
+
+            position.0 = snap.translation.adjust_precision();
+            rotation.0 = snap.rotation.adjust_precision();
+            velocity.0 = Vector::ZERO;
+            angvel.0 = Vector::ZERO;
+            let socketed = SocketedProp {
+                socket,
+                locked: config.lock,
+            };
+            let mut prop_commands = commands.entity(prop);
+            prop_commands.remove::<ReleasedProp>().try_insert(socketed);
+            if config.lock {
+                prop_commands.try_insert(RigidBody::Static);
+            }
+            w_socketed.write(PropSocketed { prop, socket });
+            occupied_sockets.insert(socket);
+            captured_props.insert(prop);
+            break;
+        }
+    }
+
+    // Props that were released too long ago to be captured by any socket
+    // don't need to be tracked anymore.
+    let max_release_window = q_socket
+        .iter()
+        .map(|(.., config)| config.release_window)
+        .fold(0.0, f32::max);
+    for (prop, .., released, _) in q_prop.iter() {
+        if released.is_some_and(|released| now - released.at > max_release_window) {
+            commands.entity(prop).remove::<ReleasedProp>();
+        }
+    }
+}
+
+/// Removes the prop from its socket, turning it back into a
+/// [`RigidBody::Dynamic`] if needed.
+pub(crate) fn unsocket(
+    commands: &mut Commands,
+    prop: Entity,
+    socketed: SocketedProp,
+    actor: Option<Entity>,
+    w_unsocketed: &mut MessageWriter<PropUnsocketed>,
+) {
+    let mut prop_commands = commands.entity(prop);
+    prop_commands
+        .remove::<SocketedProp>()
+        .try_insert(LeftSocket(socketed.socket));
+    if socketed.locked {
+        prop_commands.try_insert(RigidBody::Dynamic);
+    }
+    w_unsocketed.write(PropUnsocketed {
+        prop,
+        socket: socketed.socket,
+        actor,
+    });
+}
+
+/// Returns whether an actor may pull the socketed prop out of its socket.
+pub(crate) fn is_removable(socketed: &SocketedProp, q_socket: &Query<&PropSocket>) -> bool {
+    q_socket
+        .get(socketed.socket)
+        .ok()
+        .is_none_or(|socket| socket.removable)
+}
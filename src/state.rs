@@ -0,0 +1,275 @@
+//! Module for the state Avian Pickup keeps between physics updates.
+//! You usually don't need to touch any of this, but it is exposed for
+//! networking with rollback, replays, and the like.
+//!
+//! All of these components are inserted and removed automatically.
+//! Use [`AvianPickupSnapshot`] to save and restore them all at once.
+
+use bevy_platform::collections::HashSet;
+
+use crate::{ActorRng, input::AvianPickupInputBuffer, interaction::HeldPropShape, prelude::*};
+
+pub use crate::{
+    cooldown::Cooldown,
+    interaction::{ActorVelocity, HoldError, HoldManipulation, ShadowParams},
+    prop::{NonPickupMass, PrePickupMass, PrePickupRotation},
+    socket::{LeftSocket, ReleasedProp},
+    verb::{
+        Dropping, Freezing, Holding, Placing, Pulling, Punting, Retrieving, Stashing, Throwing,
+        Unfreezing,
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<AvianPickupTick>()
+        .register_type::<AvianPickupTick>()
+        .add_systems(
+            PhysicsSchedule,
+            advance_tick.in_set(AvianPickupSystem::Last),
+        );
+}
+
+/// The number of physics updates Avian Pickup has run so far.
+/// Used to consume [`AvianPickupInput`]s with a
+/// [`AvianPickupInput::tick`] in the right physics update.\
+/// Advanced in [`AvianPickupSystem::Last`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+#[reflect(Debug, Resource, PartialEq, Hash, Default)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct AvianPickupTick(pub u64);
+
+fn advance_tick(mut tick: ResMut<AvianPickupTick>) {
+    tick.0 += 1;
+}
+
+/// A copy of all state Avian Pickup keeps between physics updates:
+/// the components in this module and on props, the [`AvianPickupTick`],
+/// and inputs that were received but not consumed yet.\
+/// The configuration components you add yourself, e.g. [`AvianPickupActor`],
+/// are not included.
+///
+/// For rollback, take a snapshot after every physics update and restore it
+/// together with Avian's own state when rolling back. Note that the global
+/// [`RngSource`](crate::RngSource) cannot be part of the snapshot, so give
+/// every actor an [`ActorRng`](crate::ActorRng) instead.
+///
+/// # Example
+///
+/// ```no_run
+/// # use avian_pickup::state::AvianPickupSnapshot;
+/// # use bevy::prelude::*;
+/// fn rollback(world: &mut World) {
+///     let snapshot = AvianPickupSnapshot::take(world);
+///     // ... simulate ahead, receive late inputs ...
+///     snapshot.restore(world);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AvianPickupSnapshot {
+    tick: AvianPickupTick,
+    inputs: AvianPickupInputBuffer,
+    entities: Vec<(Entity, EntitySnapshot)>,
+}
+
+impl AvianPickupSnapshot {
+    /// Copies the current state of Avian Pickup out of the world.
+    pub fn take(world: &mut World) -> Self {
+        let tick = world
+            .get_resource::<AvianPickupTick>()
+            .copied()
+            .unwrap_or_default();
+        let inputs = world
+            .get_resource::<AvianPickupInputBuffer>()
+            .cloned()
+            .unwrap_or_default();
+        let mut entities: Vec<_> = world
+            .query::<EntityRef>()
+            .iter(world)
+            .map(|entity| (entity.id(), EntitySnapshot::take(entity)))
+            .filter(|(_, snapshot)| !snapshot.is_empty())
+            .collect();
+        // Query order depends on archetypes, which may differ between worlds.
+        entities.sort_by_key(|(entity, _)| *entity);
+        Self {
+            tick,
+            inputs,
+            entities,
+        }
+    }
+
+    /// Overwrites the current state of Avian Pickup with this snapshot.
+    /// Entities that were despawned since the snapshot was taken are skipped.
+    /// Entities that gained state since the snapshot was taken lose their
+    /// verbs and prop state again, and the components required by
+    /// [`AvianPickupActor`] are reset to their defaults.
+    pub fn restore(&self, world: &mut World) {
+        world.insert_resource(self.tick);
+        world.insert_resource(self.inputs.clone());
+
+        let snapshot_entities: HashSet<_> =
+            self.entities.iter().map(|(entity, _)| *entity).collect();
+        // Entities that gained state since the snapshot was taken need to lose it again.
+        let cleared_entities: Vec<_> = Self::take(world)
+            .entities
+            .into_iter()
+            .filter(|(entity, _)| !snapshot_entities.contains(entity))
+            .map(|(entity, _)| (entity, EntitySnapshot::default()))
+            .collect();
+        let entities = self.entities.iter().chain(cleared_entities.iter());
+
+        // Changing the verb markers triggers observers that update the other
+        // components, so we let them run first and overwrite their changes afterwards.
+        for (entity, snapshot) in entities.clone() {
+            if let Ok(mut entity) = world.get_entity_mut(*entity) {
+                snapshot.restore_markers(&mut entity);
+            }
+        }
+        world.flush();
+        for (entity, snapshot) in entities {
+            if let Ok(mut entity) = world.get_entity_mut(*entity) {
+                snapshot.restore_data(&mut entity);
+            }
+        }
+
+        // The cached shapes may belong to props that are no longer held.
+        let shapes: Vec<_> = world
+            .query_filtered::<Entity, With<HeldPropShape>>()
+            .iter(world)
+            .collect();
+        for entity in shapes {
+            world.entity_mut(entity).remove::<HeldPropShape>();
+        }
+    }
+
+    /// The [`AvianPickupTick`] at which the snapshot was taken.
+    pub fn tick(&self) -> AvianPickupTick {
+        self.tick
+    }
+}
+
+macro_rules! entity_snapshot {
+    (
+        markers { $($marker:ident: $marker_ty:ty),* $(,)? }
+        data { $($data:ident: $data_ty:ty),* $(,)? }
+        required { $($required:ident: $required_ty:ty),* $(,)? }
+        user { $($user:ident: $user_ty:ty),* $(,)? }
+    ) => {
+        /// The Avian Pickup state of a single entity.
+        #[derive(Debug, Clone, PartialEq, Default)]
+        struct EntitySnapshot {
+            $($marker: Option<$marker_ty>,)*
+            $($data: Option<$data_ty>,)*
+            $($required: Option<$required_ty>,)*
+            $($user: Option<$user_ty>,)*
+            /// Changed by Avian Pickup while a prop is held, but only part of
+            /// the snapshot for entities that have any other state.
+            mass: Option<Mass>,
+        }
+
+        impl EntitySnapshot {
+            fn take(entity: EntityRef) -> Self {
+                Self {
+                    $($marker: entity.get::<$marker_ty>().cloned(),)*
+                    $($data: entity.get::<$data_ty>().cloned(),)*
+                    $($required: entity.get::<$required_ty>().cloned(),)*
+                    $($user: entity.get::<$user_ty>().cloned(),)*
+                    mass: entity.get::<Mass>().cloned(),
+                }
+            }
+
+            fn is_empty(&self) -> bool {
+                true $(&& self.$marker.is_none())*
+                    $(&& self.$data.is_none())*
+                    $(&& self.$required.is_none())*
+                    $(&& self.$user.is_none())*
+            }
+
+            fn restore_markers(&self, entity: &mut EntityWorldMut) {
+                $(restore_component(entity, &self.$marker);)*
+            }
+
+            fn restore_data(&self, entity: &mut EntityWorldMut) {
+                $(restore_component(entity, &self.$data);)*
+                // Removing these would leave the actor without its required
+                // components, so reset them instead.
+                $(
+                    if self.$required.is_some() || entity.contains::<$required_ty>() {
+                        let component = self.$required.clone().unwrap_or_default();
+                        restore_component(entity, &Some(component));
+                    }
+                )*
+                // These are not ours to remove, only to restore.
+                $(
+                    if self.$user.is_some() {
+                        restore_component(entity, &self.$user);
+                    }
+                )*
+                if self.mass.is_some() {
+                    restore_component(entity, &self.mass);
+                }
+            }
+        }
+    };
+}
+
+entity_snapshot! {
+    markers {
+        throwing: Throwing,
+        dropping: Dropping,
+        pulling: Pulling,
+        holding: Holding,
+        freezing: Freezing,
+        unfreezing: Unfreezing,
+        placing: Placing,
+        stashing: Stashing,
+        retrieving: Retrieving,
+        punting: Punting,
+    }
+    data {
+        tractor_beam_length: TractorBeamLength,
+        placement_candidate: PlacementCandidate,
+        held_prop: HeldProp,
+        pre_pickup_rotation: PrePickupRotation,
+        pre_pickup_mass: PrePickupMass,
+        non_pickup_mass: NonPickupMass,
+        frozen_prop: FrozenProp,
+        stashed_prop: StashedProp,
+        socketed_prop: SocketedProp,
+        released_prop: ReleasedProp,
+        left_socket: LeftSocket,
+        last_handled_by: LastHandledBy,
+        thrown_prop: ThrownProp,
+    }
+    required {
+        state: AvianPickupActorState,
+        cooldown: Cooldown,
+        hold_error: HoldError,
+        shadow_params: ShadowParams,
+        hold_manipulation: HoldManipulation,
+        actor_velocity: ActorVelocity,
+    }
+    user {
+        inventory: PropInventory,
+        rng: ActorRng,
+    }
+}
+
+fn restore_component<T: Component + Clone + PartialEq>(
+    entity: &mut EntityWorldMut,
+    component: &Option<T>,
+) {
+    match component {
+        Some(component) => {
+            if entity.get::<T>() != Some(component) {
+                entity.insert(component.clone());
+            }
+        }
+        None => {
+            entity.remove::<T>();
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//! Module for testing gameplay built on Avian Pickup without a window.
+//! Only available with the `testing` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use avian_pickup::{prelude::*, testing::PickupTestApp};
+//! # use bevy::prelude::*;
+//! let mut app = PickupTestApp::new();
+//! app.spawn_ground();
+//! let prop = app.spawn_prop(Vec3::new(0.0, 0.25, 0.0));
+//! let actor = app.spawn_actor(
+//!     Transform::from_xyz(0.0, 1.0, 1.5).looking_at(Vec3::new(0.0, 0.25, 0.0), Vec3::Y),
+//!     AvianPickupActor::default(),
+//! );
+//!
+//! assert!(app.pull_until_holding(actor, 60));
+//! app.assert_state(actor, AvianPickupActorState::Holding(prop));
+//!
+//! app.send(actor, AvianPickupAction::Throw);
+//! app.step();
+//! app.assert_state(actor, AvianPickupActorState::Idle);
+//! assert_eq!(app.messages::<PropThrown>(), [PropThrown { prop, actor }]);
+//! ```
+
+use std::time::Duration;
+
+use bevy_app::TaskPoolPlugin;
+use bevy_ecs::message::Message;
+use bevy_time::{Fixed, Time, TimePlugin, TimeUpdateStrategy};
+use bevy_transform::TransformPlugin;
+
+use avian3d::math::{Scalar, Vector};
+
+use crate::{math::ScalarAsF32 as _, prelude::*, state::AvianPickupTick};
+
+/// A headless [`App`] with Avian and the [`AvianPickupPlugin`] that advances
+/// exactly one physics tick of 1/64 s per [`PickupTestApp::step`].
+///
+/// All messages in [`avian_pickup::output`](crate::output) are recorded and
+/// can be inspected with [`PickupTestApp::messages`]. Record your own
+/// messages with [`PickupTestApp::record_messages`].
+pub struct PickupTestApp {
+    /// The underlying app. Use this to add your own plugins and systems.
+    pub app: App,
+    length_unit: Scalar,
+}
+
+impl Default for PickupTestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PickupTestApp {
+    /// The duration of a single physics tick.
+    pub const TICK: Duration = Duration::from_nanos(1_000_000_000 / 64);
+
+    /// Creates a new app with an empty world.
+    pub fn new() -> Self {
+        Self::with_length_unit(1.0)
+    }
+
+    /// Creates a new app with an empty world whose [`PhysicsLengthUnit`] is
+    /// `length_unit`. Gravity and the props and ground spawned by this app
+    /// are scaled accordingly, so that a scene scaled by `length_unit`
+    /// behaves just like the unscaled one.
+    pub fn with_length_unit(length_unit: Scalar) -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            TimePlugin,
+            TransformPlugin,
+            PhysicsPlugins::default().with_length_unit(length_unit),
+            AvianPickupPlugin::default(),
+        ))
+        .insert_resource(Gravity(Vector::NEG_Y * 9.81 * length_unit))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Self::TICK))
+        .insert_resource(Time::<Fixed>::from_duration(Self::TICK));
+        let mut test_app = Self { app, length_unit };
+        test_app
+            .record_messages::<PropThrown>()
+            .record_messages::<PropPunted>()
+            .record_messages::<PropDropped>()
+            .record_messages::<PropFrozen>()
+            .record_messages::<PropUnfrozen>()
+            .record_messages::<PropPlaced>()
+            .record_messages::<PropPlacementFailed>()
+            .record_messages::<PropSocketed>()
+            .record_messages::<PropUnsocketed>()
+            .record_messages::<PropStashed>()
+            .record_messages::<PropRetrieved>()
+            .record_messages::<ThrownPropImpact>();
+        test_app.app.finish();
+        test_app.app.cleanup();
+        test_app
+    }
+
+    /// The world of the app.
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    /// The world of the app.
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    /// The [`PhysicsLengthUnit`] of the app.
+    pub fn length_unit(&self) -> Scalar {
+        self.length_unit
+    }
+
+    /// Spawns a large static floor whose top is at `y = 0`.
+    pub fn spawn_ground(&mut self) -> Entity {
+        let unit = self.length_unit;
+        self.world_mut()
+            .spawn((
+                RigidBody::Static,
+                Collider::cuboid(50.0 * unit, unit, 50.0 * unit),
+                Transform::from_xyz(0.0, (-0.5 * unit).f32(), 0.0),
+            ))
+            .id()
+    }
+
+    /// Spawns a dynamic cube with a side length of 0.5 length units and a
+    /// mass of 0.125 kg at `translation`.
+    /// Use [`PickupTestApp::world_mut`] to add more components to it,
+    /// e.g. a [`Mass`] or any of the overrides in
+    /// [`avian_pickup::prop`](crate::prop).
+    pub fn spawn_prop(&mut self, translation: Vec3) -> Entity {
+        let unit = self.length_unit;
+        self.world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Collider::cuboid(0.5 * unit, 0.5 * unit, 0.5 * unit),
+                // Keep the mass independent of the length unit.
+                ColliderDensity(unit.powi(-3).f32()),
+                Transform::from_translation(translation),
+            ))
+            .id()
+    }
+
+    /// Spawns an actor with the given configuration.
+    pub fn spawn_actor(&mut self, transform: Transform, config: AvianPickupActor) -> Entity {
+        self.world_mut().spawn((config, transform)).id()
+    }
+
+    /// Sends an [`AvianPickupInput`] that is handled in the next tick.
+    pub fn send(&mut self, actor: Entity, action: AvianPickupAction) -> &mut Self {
+        self.world_mut()
+            .write_message(AvianPickupInput::new(actor, action));
+        self
+    }
+
+    /// Advances the app by a single physics tick.
+    pub fn step(&mut self) -> &mut Self {
+        let tick = self.tick();
+        // The fixed timestep can skip an update when the time does not add
+        // up exactly, so we update until the tick actually advanced.
+        for _ in 0..8 {
+            self.app.update();
+            if self.tick() > tick {
+                return self;
+            }
+        }
+        panic!("Failed to advance the physics tick. Is Avian running in a fixed timestep?");
+    }
+
+    /// Advances the app by `ticks` physics ticks.
+    pub fn step_ticks(&mut self, ticks: u32) -> &mut Self {
+        for _ in 0..ticks {
+            self.step();
+        }
+        self
+    }
+
+    /// Sends `action` for `actor` in every one of the next `ticks` ticks,
+    /// like a player holding down a button.
+    pub fn send_for(&mut self, actor: Entity, action: AvianPickupAction, ticks: u32) -> &mut Self {
+        for _ in 0..ticks {
+            self.send(actor, action).step();
+        }
+        self
+    }
+
+    /// Sends [`AvianPickupAction::Pull`] until the actor holds a prop or
+    /// `max_ticks` have passed. Returns whether the actor is holding a prop.
+    pub fn pull_until_holding(&mut self, actor: Entity, max_ticks: u32) -> bool {
+        for _ in 0..max_ticks {
+            self.send(actor, AvianPickupAction::Pull).step();
+            if self.state(actor).is_holding() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// The number of physics ticks Avian Pickup has run so far.
+    pub fn tick(&self) -> u64 {
+        self.world().resource::<AvianPickupTick>().0
+    }
+
+    /// The state of the actor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `actor` is not an [`AvianPickupActor`].
+    pub fn state(&self, actor: Entity) -> AvianPickupActorState {
+        *self
+            .world()
+            .get::<AvianPickupActorState>(actor)
+            .expect("Entity is not an `AvianPickupActor`")
+    }
+
+    /// Asserts that the actor is in the `expected` state.
+    #[track_caller]
+    pub fn assert_state(&self, actor: Entity, expected: AvianPickupActorState) {
+        let state = self.state(actor);
+        assert_eq!(
+            state,
+            expected,
+            "Unexpected state of actor {actor} at tick {}",
+            self.tick()
+        );
+    }
+
+    /// Starts recording messages of type `M`.
+    /// All messages in [`avian_pickup::output`](crate::output) are recorded
+    /// by default.
+    pub fn record_messages<M: Message + Clone>(&mut self) -> &mut Self {
+        if !self.world().contains_resource::<RecordedMessages<M>>() {
+            self.app
+                .init_resource::<RecordedMessages<M>>()
+                .add_systems(Last, record_messages::<M>);
+        }
+        self
+    }
+
+    /// All messages of type `M` sent since the app was created or the
+    /// messages were last cleared, in the order they were sent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if messages of type `M` are not recorded.
+    pub fn messages<M: Message + Clone>(&self) -> &[M] {
+        &self
+            .world()
+            .get_resource::<RecordedMessages<M>>()
+            .expect("Messages are not recorded. Call `PickupTestApp::record_messages` first.")
+            .0
+    }
+
+    /// Forgets all recorded messages of type `M`.
+    pub fn clear_messages<M: Message + Clone>(&mut self) -> &mut Self {
+        if let Some(mut messages) = self.world_mut().get_resource_mut::<RecordedMessages<M>>() {
+            messages.0.clear();
+        }
+        self
+    }
+}
+
+#[derive(Resource)]
+struct RecordedMessages<M>(Vec<M>);
+
+impl<M> Default for RecordedMessages<M> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+fn record_messages<M: Message + Clone>(
+    mut reader: MessageReader<M>,
+    mut recorded: ResMut<RecordedMessages<M>>,
+) {
+    recorded.0.extend(reader.read().cloned());
+}
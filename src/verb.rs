@@ -1,6 +1,19 @@
+use bevy_ecs::query::QueryData;
+
 use crate::prelude::*;
 
-pub(super) fn plugin(_app: &mut App) {}
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Throwing>()
+        .register_type::<Dropping>()
+        .register_type::<Pulling>()
+        .register_type::<Freezing>()
+        .register_type::<Unfreezing>()
+        .register_type::<Placing>()
+        .register_type::<Stashing>()
+        .register_type::<Retrieving>()
+        .register_type::<Punting>()
+        .register_type::<Holding>();
+}
 
 /// This marks a state transition coming from either
 /// an external [`AvianPickupInput`](crate::prelude::AvianPickupInput)
@@ -11,7 +24,7 @@ pub(super) fn plugin(_app: &mut App) {}
 /// is.
 ///
 /// This type itself is just an usher for the actual marker components.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) enum Verb {
     /// Insert [`Throwing`] component and clear others
     Throw(Entity),
@@ -27,26 +40,143 @@ pub(crate) enum Verb {
     Pull,
     /// Insert [`Holding`] component and clear others
     Hold(Entity),
+    /// Insert [`Freezing`] component and clear others
+    Freeze(Entity),
+    /// Insert [`Unfreezing`] component and clear others
+    Unfreeze,
+    /// Insert [`Placing`] component and clear others
+    Place {
+        /// The prop to place
+        prop: Entity,
+        /// The global transform to place the prop at
+        transform: Transform,
+    },
+    /// Insert [`Stashing`] component and clear others
+    Stash(Entity),
+    /// Insert [`Retrieving`] component and clear others
+    Retrieve(usize),
+    /// Insert [`Punting`] component and clear others
+    Punt,
 }
 
-#[derive(Debug, Clone, Copy, Component)]
-pub(crate) struct Throwing(pub(crate) Entity);
+/// Marker inserted on an actor for the physics update in which it throws the
+/// given prop.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Throwing(#[entities] pub(crate) Entity);
 
-#[derive(Debug, Clone, Copy, Component)]
-pub(crate) struct Dropping {
+/// Marker inserted on an actor for the physics update in which it drops a
+/// prop.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Dropping {
+    #[entities]
     pub(crate) prop: Entity,
     pub(crate) forced: bool,
 }
 
-#[derive(Debug, Clone, Copy, Component)]
-pub(crate) struct Pulling;
+/// Marker inserted on an actor while it is trying to pull a prop.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Pulling;
+
+/// Marker inserted on an actor for the physics update in which it freezes the
+/// given prop.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Freezing(#[entities] pub(crate) Entity);
+
+/// Marker inserted on an actor for the physics update in which it tries to
+/// unfreeze a prop.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Unfreezing;
+
+/// Marker inserted on an actor for the physics update in which it places a
+/// prop.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Placing {
+    #[entities]
+    pub(crate) prop: Entity,
+    pub(crate) transform: Transform,
+}
+
+/// Marker inserted on an actor for the physics update in which it stashes the
+/// given prop.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Stashing(#[entities] pub(crate) Entity);
+
+/// Marker inserted on an actor for the physics update in which it retrieves
+/// the prop at the given inventory index.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Retrieving(pub(crate) usize);
+
+/// Marker inserted on an actor for the physics update in which it tries to
+/// punt a prop.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Punting;
 
 /// Component inserted on an actor when they are holding a prop.
-#[derive(Debug, Clone, Copy, Component)]
-pub struct Holding(pub Entity);
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Holding(#[entities] pub Entity);
 
 /// Sets or clears the [`Verb`] of an actor.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) struct SetVerb(pub(crate) Option<Verb>);
 
 impl SetVerb {
@@ -65,81 +195,104 @@ impl EntityCommand for SetVerb {
     }
 }
 
+/// The verb marker components currently present on an actor.
+#[derive(QueryData)]
+struct VerbMarkers {
+    throwing: Has<Throwing>,
+    dropping: Has<Dropping>,
+    pulling: Has<Pulling>,
+    holding: Has<Holding>,
+    freezing: Has<Freezing>,
+    unfreezing: Has<Unfreezing>,
+    placing: Has<Placing>,
+    stashing: Has<Stashing>,
+    retrieving: Has<Retrieving>,
+    punting: Has<Punting>,
+}
+
 fn set_verb(
     In((actor, verb)): In<(Entity, Option<Verb>)>,
     mut commands: Commands,
-    q_actor: Query<(Has<Throwing>, Has<Dropping>, Has<Pulling>, Has<Holding>)>,
+    q_actor: Query<VerbMarkers>,
 ) {
-    let Ok((throwing, dropping, pulling, holding)) = q_actor.get(actor) else {
+    let Ok(markers) = q_actor.get(actor) else {
         error!("Actor entity was deleted or in an invalid state. Ignoring.");
         return;
     };
     let mut commands = commands.entity(actor);
+    let Some(verb) = verb else {
+        // `Throwing` and `Dropping` clean up after themselves.
+        // `Holding` should never be just removed, but only replaced by `Throwing` or
+        // `Dropping`. `Pulling` in the meantime should only be present
+        // while we are holding a button -> we can remove it here.
+        if markers.pulling {
+            commands.remove::<Pulling>();
+        }
+        return;
+    };
+
     match verb {
-        Some(Verb::Throw(prop)) => {
-            if !throwing {
-                commands.try_insert(Throwing(prop));
-            }
-            if dropping {
-                commands.remove::<Dropping>();
-            }
-            if pulling {
-                commands.remove::<Pulling>();
-            }
-            if holding {
-                commands.remove::<Holding>();
-            }
+        Verb::Throw(prop) if !markers.throwing => {
+            commands.try_insert(Throwing(prop));
+        }
+        Verb::Drop { prop, forced } if !markers.dropping => {
+            commands.try_insert(Dropping { prop, forced });
+        }
+        Verb::Pull if !markers.pulling => {
+            commands.try_insert(Pulling);
+        }
+        Verb::Hold(prop) if !markers.holding => {
+            commands.try_insert(Holding(prop));
+        }
+        Verb::Freeze(prop) if !markers.freezing => {
+            commands.try_insert(Freezing(prop));
         }
-        Some(Verb::Drop { prop, forced }) => {
-            if !dropping {
-                commands.try_insert(Dropping { prop, forced });
-            }
-            if throwing {
-                commands.remove::<Throwing>();
-            }
-            if pulling {
-                commands.remove::<Pulling>();
-            }
-            if holding {
-                commands.remove::<Holding>();
-            }
+        Verb::Unfreeze if !markers.unfreezing => {
+            commands.try_insert(Unfreezing);
         }
-        Some(Verb::Pull) => {
-            if !pulling {
-                commands.try_insert(Pulling);
-            }
-            if throwing {
-                commands.remove::<Throwing>();
-            }
-            if dropping {
-                commands.remove::<Dropping>();
-            }
-            if holding {
-                commands.remove::<Holding>();
-            }
+        Verb::Place { prop, transform } if !markers.placing => {
+            commands.try_insert(Placing { prop, transform });
         }
-        Some(Verb::Hold(prop)) => {
-            if !holding {
-                commands.try_insert(Holding(prop));
-            }
-            if throwing {
-                commands.remove::<Throwing>();
-            }
-            if dropping {
-                commands.remove::<Dropping>();
-            }
-            if pulling {
-                commands.remove::<Pulling>();
-            }
+        Verb::Stash(prop) if !markers.stashing => {
+            commands.try_insert(Stashing(prop));
         }
-        None => {
-            // `Throwing` and `Dropping` clean up after themselves.
-            // `Holding` should never be just removed, but only replaced by `Throwing` or
-            // `Dropping`. `Pulling` in the meantime should only be present
-            // while we are holding a button -> we can remove it here.
-            if pulling {
-                commands.remove::<Pulling>();
-            }
+        Verb::Retrieve(index) if !markers.retrieving => {
+            commands.try_insert(Retrieving(index));
         }
+        Verb::Punt if !markers.punting => {
+            commands.try_insert(Punting);
+        }
+        _ => {}
+    }
+
+    if markers.throwing && !matches!(verb, Verb::Throw(..)) {
+        commands.remove::<Throwing>();
+    }
+    if markers.dropping && !matches!(verb, Verb::Drop { .. }) {
+        commands.remove::<Dropping>();
+    }
+    if markers.pulling && !matches!(verb, Verb::Pull) {
+        commands.remove::<Pulling>();
+    }
+    if markers.holding && !matches!(verb, Verb::Hold(..)) {
+        commands.remove::<Holding>();
+    }
+    if markers.freezing && !matches!(verb, Verb::Freeze(..)) {
+        commands.remove::<Freezing>();
+    }
+    if markers.unfreezing && !matches!(verb, Verb::Unfreeze) {
+        commands.remove::<Unfreezing>();
+    }
+    if markers.placing && !matches!(verb, Verb::Place { .. }) {
+        commands.remove::<Placing>();
+    }
+    if markers.stashing && !matches!(verb, Verb::Stash(..)) {
+        commands.remove::<Stashing>();
+    }
+    if markers.retrieving && !matches!(verb, Verb::Retrieve(..)) {
+        commands.remove::<Retrieving>();
+    }
+    if markers.punting && !matches!(verb, Verb::Punt) {
+        commands.remove::<Punting>();
     }
 }
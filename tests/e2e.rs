@@ -0,0 +1,707 @@
+//! End-to-end tests of pulling, holding, throwing and dropping props.
+#![cfg(feature = "testing")]
+
+use avian_pickup::{
+    ActorRng,
+    prelude::*,
+    prop::{FrozenProp, HandlingAction, HeldProp, LastHandledBy, ThrownProp},
+    testing::PickupTestApp,
+};
+use avian3d::{math::Vector, prelude::*};
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+
+const PROP: Vec3 = Vec3::new(0.0, 0.25, 0.0);
+
+fn setup(prop_translation: Vec3) -> (PickupTestApp, Entity, Entity) {
+    let mut app = PickupTestApp::new();
+    app.spawn_ground();
+    let prop = app.spawn_prop(prop_translation);
+    let actor = app.spawn_actor(
+        Transform::from_xyz(0.0, 1.0, 2.0).looking_at(PROP, Vec3::Y),
+        AvianPickupActor::default(),
+    );
+    (app, actor, prop)
+}
+
+#[test]
+fn pulling_a_prop_holds_it() {
+    let (mut app, actor, prop) = setup(PROP);
+    app.assert_state(actor, AvianPickupActorState::Idle);
+
+    assert!(app.pull_until_holding(actor, 120));
+    app.assert_state(actor, AvianPickupActorState::Holding(prop));
+    assert!(app.world().get::<HeldProp>(prop).is_some());
+
+    // Keeps holding without any more input.
+    app.step_ticks(30);
+    app.assert_state(actor, AvianPickupActorState::Holding(prop));
+}
+
+#[test]
+fn throwing_a_held_prop_launches_it() {
+    let (mut app, actor, prop) = setup(PROP);
+    assert!(app.pull_until_holding(actor, 120));
+
+    app.send(actor, AvianPickupAction::Throw).step();
+    app.assert_state(actor, AvianPickupActorState::Idle);
+    assert_eq!(app.messages::<PropThrown>(), [PropThrown { prop, actor }]);
+    assert!(app.world().get::<HeldProp>(prop).is_none());
+
+    let forward = app.world().get::<GlobalTransform>(actor).unwrap().forward();
+    let velocity = app.world().get::<LinearVelocity>(prop).unwrap().0;
+    assert!(
+        velocity.dot(forward.into()) > 1.0,
+        "Prop was not thrown forward: {velocity}"
+    );
+}
+
+#[test]
+fn dropping_a_held_prop_restores_its_mass() {
+    let (mut app, actor, prop) = setup(PROP);
+    app.world_mut().entity_mut(prop).insert(Mass(2.0));
+    assert!(app.pull_until_holding(actor, 120));
+    assert_ne!(app.world().get::<Mass>(prop), Some(&Mass(2.0)));
+    // Let the drop cooldown after picking up run out.
+    app.step_ticks(30);
+
+    app.send_for(actor, AvianPickupAction::Drop, 10);
+    app.assert_state(actor, AvianPickupActorState::Idle);
+    assert_eq!(
+        app.messages::<PropDropped>(),
+        [PropDropped {
+            prop,
+            actor,
+            forced: false
+        }]
+    );
+    assert!(app.messages::<PropThrown>().is_empty());
+    assert_eq!(app.world().get::<Mass>(prop), Some(&Mass(2.0)));
+}
+
+#[test]
+fn frozen_props_hang_in_the_air_until_unfrozen() {
+    let (mut app, actor, prop) = setup(PROP);
+    assert!(app.pull_until_holding(actor, 120));
+    app.step_ticks(30);
+
+    app.send(actor, AvianPickupAction::Freeze).step();
+    app.assert_state(actor, AvianPickupActorState::Idle);
+    assert_eq!(app.messages::<PropFrozen>(), [PropFrozen { actor, prop }]);
+    assert!(app.world().get::<HeldProp>(prop).is_none());
+    assert_eq!(
+        app.world().get::<FrozenProp>(prop),
+        Some(&FrozenProp { by: actor })
+    );
+
+    let frozen_translation = app.world().get::<Position>(prop).unwrap().0;
+    assert!(
+        frozen_translation.y > PROP.y + 0.1,
+        "Prop was not frozen mid-air"
+    );
+    app.step_ticks(60);
+    assert_eq!(
+        app.world().get::<Position>(prop).unwrap().0,
+        frozen_translation
+    );
+
+    app.send_for(actor, AvianPickupAction::Unfreeze, 10);
+    assert_eq!(
+        app.messages::<PropUnfrozen>(),
+        [PropUnfrozen { actor, prop }]
+    );
+    assert!(app.world().get::<FrozenProp>(prop).is_none());
+    assert_eq!(
+        app.world().get::<RigidBody>(prop),
+        Some(&RigidBody::Dynamic)
+    );
+    app.assert_state(actor, AvianPickupActorState::Idle);
+
+    // Falls down again.
+    app.step_ticks(60);
+    let translation = app.world().get::<Position>(prop).unwrap().0;
+    assert!(
+        translation.y < frozen_translation.y - 0.1,
+        "Prop did not fall: {translation}"
+    );
+}
+
+#[test]
+fn props_are_placed_on_flat_surfaces() {
+    let (mut app, actor, prop) = setup(PROP);
+    assert!(app.pull_until_holding(actor, 120));
+
+    app.send(actor, AvianPickupAction::Place).step();
+    app.assert_state(actor, AvianPickupActorState::Idle);
+    assert_eq!(app.messages::<PropPlaced>(), [PropPlaced { prop, actor }]);
+    assert!(app.messages::<PropPlacementFailed>().is_empty());
+    assert!(app.world().get::<HeldProp>(prop).is_none());
+
+    // Rests on the ground where the actor is looking.
+    app.step_ticks(30);
+    let translation = app.world().get::<Position>(prop).unwrap().0;
+    assert!(
+        translation.distance(PROP) < 0.1,
+        "Prop was not placed on the ground: {translation}"
+    );
+}
+
+#[test]
+fn props_are_not_placed_on_steep_surfaces() {
+    let (mut app, actor, prop) = setup(PROP);
+    assert!(app.pull_until_holding(actor, 120));
+
+    // Look straight at a wall instead of the ground.
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Collider::cuboid(4.0, 4.0, 0.1),
+        Transform::from_xyz(0.0, 2.0, -0.5),
+    ));
+    app.world_mut()
+        .entity_mut(actor)
+        .insert(Transform::from_xyz(0.0, 1.0, 2.0).looking_to(Vec3::NEG_Z, Vec3::Y));
+    app.step_ticks(10);
+
+    app.send(actor, AvianPickupAction::Place).step();
+    app.assert_state(actor, AvianPickupActorState::Holding(prop));
+    assert!(app.messages::<PropPlaced>().is_empty());
+    assert_eq!(
+        app.messages::<PropPlacementFailed>(),
+        [PropPlacementFailed {
+            prop,
+            actor,
+            reason: PlacementError::TooSteep
+        }]
+    );
+}
+
+#[test]
+fn inputs_are_active_in_every_physics_update_of_their_frame() {
+    /// Pulls the prop, recording the state of the actor and the position of
+    /// the prop every third tick.
+    fn run(ticks_per_frame: u32) -> Vec<(AvianPickupActorState, Vec3)> {
+        let (mut app, actor, prop) = setup(PROP);
+        app.world_mut()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(
+                PickupTestApp::TICK * ticks_per_frame,
+            ));
+        let mut frames = Vec::new();
+        while app.tick() < 120 {
+            app.send(actor, AvianPickupAction::Pull).step();
+            if app.tick() % 3 == 0 {
+                let position = app.world().get::<Position>(prop).unwrap().0;
+                frames.push((app.state(actor), position));
+            }
+        }
+        frames
+    }
+
+    // Sending an input once per frame behaves like sending it in every tick.
+    let frames = run(3);
+    assert!(
+        frames.iter().any(|(state, _)| state.is_holding()),
+        "The prop was never held"
+    );
+    assert_eq!(frames, run(1));
+}
+
+#[test]
+fn thrown_props_inherit_the_velocity_of_the_actor_vehicle() {
+    let mut app = PickupTestApp::new();
+    app.spawn_ground();
+    let prop = app.spawn_prop(PROP);
+    let actor = app.spawn_actor(
+        Transform::from_xyz(0.0, 1.0, 2.0).looking_at(PROP, Vec3::Y),
+        AvianPickupActor {
+            throw: AvianPickupActorThrowConfig {
+                // Only throw with the inherited velocity.
+                linear_speed_range: 0.0..=0.0,
+                velocity_inheritance: 1.0,
+                inherit_angular_velocity: true,
+                ..default()
+            },
+            ..default()
+        },
+    );
+    assert!(app.pull_until_holding(actor, 120));
+
+    let vehicle = app
+        .world_mut()
+        .spawn((
+            RigidBody::Kinematic,
+            Transform::default(),
+            LinearVelocity(Vector::X * 2.0),
+            AngularVelocity(Vector::Y * 0.5),
+            // Rotate around a point away from the vehicle's origin.
+            Mass(1.0),
+            CenterOfMass::new(0.0, 0.0, -3.0),
+        ))
+        .id();
+    app.world_mut().entity_mut(actor).insert(ChildOf(vehicle));
+    app.step_ticks(2);
+
+    let vehicle_position = app.world().get::<Position>(vehicle).unwrap().0;
+    let vehicle_rotation = *app.world().get::<Rotation>(vehicle).unwrap();
+    let center_of_mass = app.world().get::<ComputedCenterOfMass>(vehicle).unwrap().0;
+    let prop_position = app.world().get::<Position>(prop).unwrap().0;
+    let expected = Vector::X * 2.0
+        + (Vector::Y * 0.5)
+            .cross(prop_position - (vehicle_position + vehicle_rotation * center_of_mass));
+
+    app.send(actor, AvianPickupAction::Throw).step();
+    app.assert_state(actor, AvianPickupActorState::Idle);
+    let velocity = app.world().get::<LinearVelocity>(prop).unwrap().0;
+    assert!(
+        velocity.with_y(0.0).abs_diff_eq(expected.with_y(0.0), 0.1),
+        "Prop did not inherit the vehicle's velocity: expected {expected}, got {velocity}"
+    );
+}
+
+#[test]
+fn retrieved_props_are_not_placed_inside_obstacles() {
+    let (mut app, actor, prop) = setup(PROP);
+    app.world_mut()
+        .entity_mut(actor)
+        .insert(PropInventory::default());
+    assert!(app.pull_until_holding(actor, 120));
+    // Let the drop cooldown after picking up run out.
+    app.step_ticks(30);
+    app.send(actor, AvianPickupAction::Stash).step();
+    assert_eq!(app.messages::<PropStashed>(), [PropStashed { prop, actor }]);
+
+    // Face a wall that is closer than the preferred hold distance.
+    app.world_mut()
+        .entity_mut(actor)
+        .insert(Transform::from_xyz(0.0, 1.0, 2.0).looking_at(Vec3::new(0.0, 1.0, -10.0), Vec3::Y));
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Collider::cuboid(4.0, 4.0, 0.2),
+        Transform::from_xyz(0.0, 1.0, 1.5),
+    ));
+    app.step();
+
+    app.send(actor, AvianPickupAction::Retrieve(0)).step();
+    assert_eq!(
+        app.messages::<PropRetrieved>(),
+        [PropRetrieved { prop, actor }]
+    );
+    app.assert_state(actor, AvianPickupActorState::Holding(prop));
+    let position = app.world().get::<Position>(prop).unwrap().0;
+    assert!(
+        position.z >= 1.6 + 0.25 - 0.01,
+        "Prop was retrieved inside the wall: {position}"
+    );
+}
+
+#[test]
+#[cfg(feature = "visibility")]
+fn stashed_props_are_hidden_until_retrieved() {
+    let (mut app, actor, prop) = setup(PROP);
+    app.world_mut()
+        .entity_mut(actor)
+        .insert(PropInventory::default());
+    app.world_mut().entity_mut(prop).insert(Visibility::Visible);
+    assert!(app.pull_until_holding(actor, 120));
+    app.step_ticks(30);
+
+    app.send(actor, AvianPickupAction::Stash).step();
+    assert_eq!(
+        app.world().get::<Visibility>(prop),
+        Some(&Visibility::Hidden)
+    );
+
+    app.send(actor, AvianPickupAction::Retrieve(0)).step();
+    app.assert_state(actor, AvianPickupActorState::Holding(prop));
+    assert_eq!(
+        app.world().get::<Visibility>(prop),
+        Some(&Visibility::Visible)
+    );
+}
+
+#[test]
+fn released_props_remember_who_released_them() {
+    for (action, handling) in [
+        (AvianPickupAction::Throw, HandlingAction::Thrown),
+        (AvianPickupAction::Drop, HandlingAction::Dropped),
+        (AvianPickupAction::Freeze, HandlingAction::Frozen),
+        (AvianPickupAction::Place, HandlingAction::Placed),
+        (AvianPickupAction::Stash, HandlingAction::Stashed),
+    ] {
+        let (mut app, actor, prop) = setup(PROP);
+        app.world_mut()
+            .entity_mut(actor)
+            .insert(PropInventory::default());
+        assert!(app.pull_until_holding(actor, 120));
+        app.step_ticks(30);
+        assert!(app.world().get::<LastHandledBy>(prop).is_none());
+
+        app.send(actor, action).step();
+        app.assert_state(actor, AvianPickupActorState::Idle);
+        let last_handled_by = app.world().get::<LastHandledBy>(prop);
+        assert_eq!(
+            last_handled_by.map(|last| (last.actor, last.action)),
+            Some((actor, handling)),
+            "Wrong attribution after {action:?}"
+        );
+    }
+}
+
+#[test]
+fn punting_a_prop_launches_it_without_holding_it() {
+    let (mut app, actor, prop) = setup(PROP);
+    // Let the prop settle on the ground.
+    app.step_ticks(10);
+
+    app.send(actor, AvianPickupAction::Punt).step();
+    app.assert_state(actor, AvianPickupActorState::Idle);
+    assert_eq!(app.messages::<PropPunted>(), [PropPunted { prop, actor }]);
+    assert!(app.world().get::<HeldProp>(prop).is_none());
+    let last_handled_by = app.world().get::<LastHandledBy>(prop).unwrap();
+    assert_eq!(
+        (last_handled_by.actor, last_handled_by.action),
+        (actor, HandlingAction::Punted)
+    );
+
+    let velocity = app.world().get::<LinearVelocity>(prop).unwrap().0;
+    assert!(velocity.z < -1.0, "Prop was not punted away: {velocity}");
+}
+
+#[test]
+fn props_punted_twice_stop_reporting_collisions_afterwards() {
+    let mut app = PickupTestApp::new();
+    app.spawn_ground();
+    let prop = app.spawn_prop(PROP);
+    let actor = app.spawn_actor(
+        Transform::from_xyz(0.0, 1.0, 2.0).looking_at(PROP, Vec3::Y),
+        AvianPickupActor {
+            throw: AvianPickupActorThrowConfig {
+                track_impacts: true,
+                impact_tracking_timeout: 1.0,
+                ..default()
+            },
+            ..default()
+        },
+    );
+    app.step_ticks(10);
+
+    app.send(actor, AvianPickupAction::Punt).step();
+    app.send(actor, AvianPickupAction::Punt).step();
+    assert_eq!(app.messages::<PropPunted>().len(), 2);
+    assert!(app.world().get::<ThrownProp>(prop).is_some());
+
+    app.step_ticks(120);
+    assert!(app.world().get::<ThrownProp>(prop).is_none());
+    let colliders = app.world().get::<RigidBodyColliders>(prop).unwrap();
+    for collider in colliders.iter() {
+        assert!(
+            app.world()
+                .get::<CollisionEventsEnabled>(collider)
+                .is_none(),
+            "Collider {collider} still reports collisions"
+        );
+    }
+}
+
+#[test]
+fn props_that_are_too_heavy_are_not_pulled() {
+    let (mut app, actor, prop) = setup(PROP);
+    app.world_mut().entity_mut(prop).insert(Mass(1000.0));
+
+    assert!(!app.pull_until_holding(actor, 120));
+    app.assert_state(actor, AvianPickupActorState::Idle);
+}
+
+#[test]
+fn props_out_of_reach_are_not_pulled() {
+    let (mut app, actor, _prop) = setup(PROP + Vec3::NEG_Z * 20.0);
+
+    assert!(!app.pull_until_holding(actor, 120));
+    app.assert_state(actor, AvianPickupActorState::Idle);
+}
+
+#[test]
+fn props_can_be_picked_up_again_after_throwing() {
+    let (mut app, actor, prop) = setup(PROP);
+    assert!(app.pull_until_holding(actor, 120));
+    app.send(actor, AvianPickupAction::Throw).step();
+    // Let the prop land and the throw cooldown run out.
+    app.step_ticks(120);
+
+    // Look at wherever the prop ended up.
+    let prop_translation = app.world().get::<Position>(prop).unwrap().0;
+    let actor_translation = prop_translation + Vec3::new(0.0, 0.75, 1.5);
+    app.world_mut().entity_mut(actor).insert(
+        Transform::from_translation(actor_translation).looking_at(prop_translation, Vec3::Y),
+    );
+    assert!(app.pull_until_holding(actor, 120));
+    app.assert_state(actor, AvianPickupActorState::Holding(prop));
+}
+
+#[test]
+fn held_props_follow_the_hold_target_override() {
+    let (mut app, actor, prop) = setup(PROP);
+    assert!(app.pull_until_holding(actor, 120));
+
+    let hand_translation = Vec3::new(0.5, 1.0, 1.0);
+    let hand = app
+        .world_mut()
+        .spawn(Transform::from_translation(hand_translation))
+        .id();
+    app.world_mut()
+        .entity_mut(actor)
+        .insert(HoldTargetOverride::Entity(hand));
+    app.step_ticks(60);
+
+    app.assert_state(actor, AvianPickupActorState::Holding(prop));
+    let prop_translation = app.world().get::<Position>(prop).unwrap().0;
+    assert!(
+        prop_translation.distance(hand_translation) < 0.1,
+        "Prop is not held at the hand: {prop_translation}"
+    );
+}
+
+#[test]
+fn props_are_found_through_the_aim_origin() {
+    let (mut app, actor, prop) = setup(PROP);
+    // The character looks away from the prop, but its camera looks at it.
+    app.world_mut()
+        .entity_mut(actor)
+        .insert(Transform::from_xyz(0.0, 1.0, 2.0).looking_to(Vec3::Z, Vec3::Y));
+    let camera = app
+        .world_mut()
+        .spawn(Transform::from_xyz(0.0, 1.5, 2.5).looking_at(PROP, Vec3::Y))
+        .id();
+    app.world_mut()
+        .entity_mut(actor)
+        .insert(PickupAimOrigin(camera));
+
+    assert!(app.pull_until_holding(actor, 120));
+    app.assert_state(actor, AvianPickupActorState::Holding(prop));
+}
+
+#[test]
+fn props_out_of_sight_of_the_hold_anchor_are_not_pulled() {
+    let (mut app, actor, _prop) = setup(PROP);
+    // A low wall hides the prop from the character, but not from its camera.
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Collider::cuboid(2.0, 1.2, 0.1),
+        Transform::from_xyz(0.0, 0.6, 1.0),
+    ));
+    let camera = app
+        .world_mut()
+        .spawn(Transform::from_xyz(0.0, 3.0, 2.0).looking_at(PROP, Vec3::Y))
+        .id();
+    app.world_mut()
+        .entity_mut(actor)
+        .insert((PickupAimOrigin(camera), PickupHoldAnchor(actor)));
+
+    assert!(!app.pull_until_holding(actor, 120));
+    app.assert_state(actor, AvianPickupActorState::Idle);
+}
+
+#[test]
+fn the_hold_anchor_sees_through_the_actor_body() {
+    let (mut app, actor, prop) = setup(PROP);
+    // The character's body surrounds the hold anchor.
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Kinematic,
+            Collider::capsule(0.3, 1.0),
+            Transform::from_xyz(0.0, 1.0, 2.0),
+        ))
+        .id();
+    let camera = app
+        .world_mut()
+        .spawn(Transform::from_xyz(0.0, 2.5, 1.5).looking_at(PROP, Vec3::Y))
+        .id();
+    app.world_mut().entity_mut(actor).insert((
+        Transform::default().looking_at(PROP - Vec3::new(0.0, 1.0, 2.0), Vec3::Y),
+        ChildOf(body),
+        PickupAimOrigin(camera),
+        PickupHoldAnchor(actor),
+    ));
+
+    assert!(app.pull_until_holding(actor, 120));
+    app.assert_state(actor, AvianPickupActorState::Holding(prop));
+}
+
+#[test]
+fn large_props_peeking_out_from_behind_cover_are_pulled() {
+    let mut app = PickupTestApp::new();
+    app.spawn_ground();
+    let prop = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::cuboid(1.0, 1.0, 1.0),
+            Transform::from_xyz(0.0, 0.5, -0.5),
+        ))
+        .id();
+    // The wall hides the prop's center from the character, but not its top.
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Collider::cuboid(2.0, 0.8, 0.1),
+        Transform::from_xyz(0.0, 0.4, 0.5),
+    ));
+    let actor = app.spawn_actor(
+        Transform::from_xyz(0.0, 1.0, 2.0).looking_at(Vec3::new(0.0, 0.5, -0.5), Vec3::Y),
+        AvianPickupActor::default(),
+    );
+    let camera = app
+        .world_mut()
+        .spawn(Transform::from_xyz(0.0, 3.0, 2.0).looking_at(Vec3::new(0.0, 0.5, -0.5), Vec3::Y))
+        .id();
+    app.world_mut()
+        .entity_mut(actor)
+        .insert((PickupAimOrigin(camera), PickupHoldAnchor(actor)));
+
+    assert!(app.pull_until_holding(actor, 120));
+    app.assert_state(actor, AvianPickupActorState::Holding(prop));
+}
+
+#[test]
+fn tractor_beams_hold_props_where_they_were_grabbed() {
+    let mut app = PickupTestApp::new();
+    // Floating in the air, so that nothing stops the prop from moving away.
+    let prop_translation = Vec3::new(0.0, 1.0, 0.0);
+    let prop = app.spawn_prop(prop_translation);
+    app.world_mut().entity_mut(prop).insert(GravityScale(0.0));
+    let actor_translation = Vec3::new(0.0, 1.0, 2.5);
+    // Aim next to the prop so that it is found in the interaction cone.
+    let actor = app.spawn_actor(
+        Transform::from_translation(actor_translation)
+            .looking_at(prop_translation + Vec3::X * 0.45, Vec3::Y),
+        AvianPickupActor {
+            hold: AvianPickupActorHoldConfig {
+                mode: AvianPickupHoldMode::TractorBeam,
+                ..default()
+            },
+            ..default()
+        },
+    );
+    app.step_ticks(10);
+    let grabbed_distance = app
+        .world()
+        .get::<Position>(prop)
+        .unwrap()
+        .0
+        .distance(actor_translation);
+
+    assert!(app.pull_until_holding(actor, 120));
+    app.step_ticks(30);
+    let held_distance = app
+        .world()
+        .get::<Position>(prop)
+        .unwrap()
+        .0
+        .distance(actor_translation);
+    assert!(
+        (held_distance - grabbed_distance).abs() < 0.1,
+        "Prop grabbed at {grabbed_distance} is held at {held_distance}"
+    );
+}
+
+#[test]
+fn props_held_on_the_ground_plane_follow_the_aim_ray() {
+    let mut app = PickupTestApp::new();
+    app.spawn_ground();
+    let prop = app.spawn_prop(PROP);
+    let actor = app.spawn_actor(
+        Transform::from_xyz(0.0, 1.0, 2.0),
+        AvianPickupActor {
+            interaction_distance: 20.0,
+            hold: AvianPickupActorHoldConfig {
+                mode: AvianPickupHoldMode::GroundPlane,
+                ground_plane_height: 1.0,
+                ..default()
+            },
+            ..default()
+        },
+    );
+    // Looking straight down at the prop from above, like a top-down camera.
+    let cursor_origin = Vec3::new(0.0, 10.0, 0.0);
+    app.world_mut()
+        .entity_mut(actor)
+        .insert(AimRayOverride(Ray3d::new(cursor_origin, Dir3::NEG_Y)));
+    assert!(app.pull_until_holding(actor, 120));
+    app.assert_state(actor, AvianPickupActorState::Holding(prop));
+
+    let direction = Dir3::new(Vec3::new(2.0, -10.0, 0.0)).unwrap();
+    app.world_mut()
+        .entity_mut(actor)
+        .insert(AimRayOverride(Ray3d::new(cursor_origin, direction)));
+    app.step_ticks(60);
+
+    app.assert_state(actor, AvianPickupActorState::Holding(prop));
+    let prop_translation = app.world().get::<Position>(prop).unwrap().0;
+    let expected = Vec3::new(1.8, 1.0, 0.0);
+    assert!(
+        prop_translation.distance(expected) < 0.1,
+        "Prop is not held where the aim ray crosses the plane: {prop_translation}"
+    );
+}
+
+#[test]
+fn scenes_scaled_by_the_length_unit_behave_identically() {
+    /// Pulls, holds and throws the prop, recording the state of the actor and
+    /// the position of the prop in length units after every tick.
+    fn run(length_unit: f32) -> Vec<(AvianPickupActorState, Vec3)> {
+        let mut app = PickupTestApp::with_length_unit(length_unit);
+        app.spawn_ground();
+        let prop = app.spawn_prop(PROP * length_unit);
+        let actor = app.spawn_actor(
+            Transform::from_xyz(0.0, length_unit, 2.0 * length_unit)
+                .looking_at(PROP * length_unit, Vec3::Y),
+            AvianPickupActor::default(),
+        );
+        // Both runs need to spin the thrown prop the same way.
+        app.world_mut()
+            .entity_mut(actor)
+            .insert(ActorRng::from_seed(42));
+        let record = |app: &PickupTestApp| {
+            let position = app.world().get::<Position>(prop).unwrap().0 / length_unit;
+            (app.state(actor), position)
+        };
+        let mut frames = Vec::new();
+        for _ in 0..60 {
+            if !app.state(actor).is_holding() {
+                app.send(actor, AvianPickupAction::Pull);
+            }
+            app.step();
+            frames.push(record(&app));
+        }
+        for _ in 0..30 {
+            app.step();
+            frames.push(record(&app));
+        }
+        app.send(actor, AvianPickupAction::Throw).step();
+        for _ in 0..30 {
+            app.step();
+            frames.push(record(&app));
+        }
+        frames
+    }
+
+    let unscaled = run(1.0);
+    let scaled = run(100.0);
+    assert!(
+        unscaled
+            .iter()
+            .any(|(state, _)| matches!(state, AvianPickupActorState::Holding(..))),
+        "The prop was never held"
+    );
+    for (tick, ((unscaled_state, unscaled_position), (scaled_state, scaled_position))) in
+        unscaled.iter().zip(&scaled).enumerate()
+    {
+        assert_eq!(
+            std::mem::discriminant(unscaled_state),
+            std::mem::discriminant(scaled_state),
+            "States differ at tick {tick}"
+        );
+        assert!(
+            unscaled_position.distance(*scaled_position) < 0.05,
+            "Positions differ at tick {tick}: {unscaled_position} vs {scaled_position}"
+        );
+    }
+}
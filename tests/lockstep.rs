@@ -0,0 +1,271 @@
+//! Makes sure that Avian Pickup behaves deterministically, which is needed
+//! for networking with rollback.
+
+use std::time::Duration;
+
+use avian_pickup::{
+    ActorRng,
+    prelude::*,
+    prop::{HeldProp, LastHandledBy},
+    state::{
+        ActorVelocity, AvianPickupSnapshot, AvianPickupTick, Cooldown, HoldError, HoldManipulation,
+        Holding, PrePickupRotation, ShadowParams,
+    },
+};
+use avian3d::prelude::*;
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+
+const PULL_TICKS: std::ops::Range<u64> = 2..6;
+const THROW_TICK: u64 = 40;
+const TICKS: u64 = 80;
+
+struct Scene {
+    app: App,
+    actor: Entity,
+    prop: Entity,
+}
+
+fn setup() -> Scene {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        TransformPlugin,
+        AssetPlugin::default(),
+        PhysicsPlugins::default(),
+        AvianPickupPlugin::default(),
+    ))
+    .init_asset::<Mesh>()
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+        1.0 / 64.0,
+    )));
+
+    let world = app.world_mut();
+    world.spawn((
+        RigidBody::Static,
+        Collider::cuboid(20.0, 1.0, 20.0),
+        Transform::from_xyz(0.0, -0.5, 0.0),
+    ));
+    let prop = world
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::cuboid(0.5, 0.5, 0.5),
+            Transform::from_xyz(0.0, 0.25, 0.0),
+            SleepingDisabled,
+        ))
+        .id();
+    let actor = world
+        .spawn((
+            AvianPickupActor {
+                hold: AvianPickupActorHoldConfig {
+                    mode: AvianPickupHoldMode::TractorBeam,
+                    ..default()
+                },
+                ..default()
+            },
+            // Throws must not depend on the thread-local RNG.
+            ActorRng::from_seed(42),
+            Transform::from_xyz(0.0, 1.0, 3.0).looking_at(Vec3::new(0.0, 0.25, 0.0), Vec3::Y),
+        ))
+        .id();
+
+    app.finish();
+    app.cleanup();
+    Scene { app, actor, prop }
+}
+
+/// Sends all inputs of the script up front, so this also covers inputs that
+/// are held back until their tick comes.
+fn send_script(scene: &mut Scene) {
+    let actor = scene.actor;
+    let world = scene.app.world_mut();
+    for tick in PULL_TICKS {
+        world.write_message(AvianPickupInput::new(actor, AvianPickupAction::Pull).with_tick(tick));
+    }
+    world.write_message(
+        AvianPickupInput::new(actor, AvianPickupAction::Throw).with_tick(THROW_TICK),
+    );
+}
+
+fn physics_state(scene: &Scene) -> (Position, Rotation, LinearVelocity, AngularVelocity) {
+    let prop = scene.app.world().entity(scene.prop);
+    (
+        *prop.get::<Position>().unwrap(),
+        *prop.get::<Rotation>().unwrap(),
+        *prop.get::<LinearVelocity>().unwrap(),
+        *prop.get::<AngularVelocity>().unwrap(),
+    )
+}
+
+fn restore_physics_state(
+    scene: &mut Scene,
+    (position, rotation, linvel, angvel): (Position, Rotation, LinearVelocity, AngularVelocity),
+) {
+    let prop = scene.prop;
+    scene.app.world_mut().entity_mut(prop).insert((
+        position,
+        rotation,
+        linvel,
+        angvel,
+        // Avian syncs changed transforms back to the position.
+        Transform::from_translation(position.0).with_rotation(rotation.0),
+    ));
+}
+
+fn tick(scene: &Scene) -> u64 {
+    scene.app.world().resource::<AvianPickupTick>().0
+}
+
+fn run_until(scene: &mut Scene, until: u64) {
+    // Guard against the fixed timestep not running in some updates.
+    let mut updates = 0;
+    while tick(scene) < until {
+        scene.app.update();
+        updates += 1;
+        assert!(updates < 10 * TICKS, "Physics did not advance");
+    }
+}
+
+#[test]
+fn two_apps_in_lockstep_stay_identical() {
+    let mut a = setup();
+    let mut b = setup();
+    send_script(&mut a);
+    send_script(&mut b);
+
+    let mut was_holding = false;
+    while tick(&a) < TICKS {
+        a.app.update();
+        b.app.update();
+        assert_eq!(tick(&a), tick(&b));
+        assert_eq!(physics_state(&a), physics_state(&b), "at tick {}", tick(&a));
+        let state_a = *a.app.world().get::<AvianPickupActorState>(a.actor).unwrap();
+        let state_b = *b.app.world().get::<AvianPickupActorState>(b.actor).unwrap();
+        assert_eq!(state_a, state_b, "at tick {}", tick(&a));
+        was_holding |= state_a.is_holding();
+    }
+    assert!(was_holding, "The actor never picked up the prop");
+    assert!(
+        a.app.world().get::<HeldProp>(a.prop).is_none(),
+        "The prop was never thrown"
+    );
+}
+
+#[test]
+fn restoring_a_snapshot_replays_identically() {
+    let mut scene = setup();
+    send_script(&mut scene);
+    // Hold the prop for a while, but restore before it is thrown.
+    run_until(&mut scene, THROW_TICK - 10);
+    assert!(
+        scene
+            .app
+            .world()
+            .get::<AvianPickupActorState>(scene.actor)
+            .unwrap()
+            .is_holding()
+    );
+    let snapshot = AvianPickupSnapshot::take(scene.app.world_mut());
+    let physics = physics_state(&scene);
+
+    run_until(&mut scene, THROW_TICK + 10);
+    let expected = physics_state(&scene);
+
+    snapshot.restore(scene.app.world_mut());
+    restore_physics_state(&mut scene, physics);
+    assert_eq!(AvianPickupSnapshot::take(scene.app.world_mut()), snapshot);
+
+    run_until(&mut scene, THROW_TICK + 10);
+    // Avian's contact cache is not part of the restored physics state, so the
+    // warm started contacts with the ground differ by a tiny bit.
+    let (position, rotation, linvel, angvel) = physics_state(&scene);
+    let (expected_position, expected_rotation, expected_linvel, expected_angvel) = expected;
+    assert!(position.abs_diff_eq(expected_position.0, 1e-4));
+    assert!(rotation.abs_diff_eq(expected_rotation.0, 1e-4));
+    assert!(linvel.abs_diff_eq(expected_linvel.0, 1e-3));
+    assert!(angvel.abs_diff_eq(expected_angvel.0, 1e-3));
+}
+
+#[test]
+fn restoring_a_snapshot_resets_actors_spawned_after_it() {
+    let mut scene = setup();
+    run_until(&mut scene, 2);
+    let snapshot = AvianPickupSnapshot::take(scene.app.world_mut());
+    let prop_mass = scene.app.world().get::<Mass>(scene.prop).copied();
+
+    let mut inventory = PropInventory::default();
+    inventory.capacity = 2;
+    let late_actor = scene
+        .app
+        .world_mut()
+        .spawn((
+            AvianPickupActor::default(),
+            ActorRng::from_seed(7),
+            inventory.clone(),
+            Transform::from_xyz(0.0, 1.0, -3.0).looking_at(Vec3::new(0.0, 0.25, 0.0), Vec3::Y),
+        ))
+        .id();
+    for tick in 3..8 {
+        scene.app.world_mut().write_message(
+            AvianPickupInput::new(late_actor, AvianPickupAction::Pull).with_tick(tick),
+        );
+    }
+    run_until(&mut scene, 20);
+    let world = scene.app.world();
+    assert!(
+        world
+            .get::<AvianPickupActorState>(late_actor)
+            .unwrap()
+            .is_holding()
+    );
+    let rng = world.get::<ActorRng>(late_actor).unwrap().clone();
+
+    snapshot.restore(scene.app.world_mut());
+    let world = scene.app.world();
+    let actor = world.entity(late_actor);
+    // The components required by the actor are reset, not removed.
+    assert_eq!(
+        actor.get::<AvianPickupActorState>(),
+        Some(&AvianPickupActorState::Idle)
+    );
+    assert_eq!(actor.get::<Cooldown>(), Some(&Cooldown::default()));
+    assert_eq!(actor.get::<HoldError>(), Some(&HoldError::default()));
+    assert_eq!(actor.get::<ShadowParams>(), Some(&ShadowParams::default()));
+    assert_eq!(
+        actor.get::<HoldManipulation>(),
+        Some(&HoldManipulation::default())
+    );
+    assert_eq!(
+        actor.get::<ActorVelocity>(),
+        Some(&ActorVelocity::default())
+    );
+    assert!(!actor.contains::<Holding>());
+    // Components added by the user are left alone.
+    assert_eq!(actor.get::<ActorRng>(), Some(&rng));
+    assert_eq!(actor.get::<PropInventory>(), Some(&inventory));
+    // The prop is no longer held.
+    let prop = world.entity(scene.prop);
+    assert!(!prop.contains::<HeldProp>());
+    assert!(!prop.contains::<PrePickupRotation>());
+    assert!(!prop.contains::<LastHandledBy>());
+    assert_eq!(prop.get::<Mass>().copied(), prop_mass);
+    assert_eq!(
+        AvianPickupSnapshot::take(scene.app.world_mut()).tick(),
+        snapshot.tick()
+    );
+
+    // The actor can pick the prop up again from scratch.
+    for tick in 3..8 {
+        scene.app.world_mut().write_message(
+            AvianPickupInput::new(late_actor, AvianPickupAction::Pull).with_tick(tick),
+        );
+    }
+    run_until(&mut scene, 20);
+    assert!(
+        scene
+            .app
+            .world()
+            .get::<AvianPickupActorState>(late_actor)
+            .unwrap()
+            .is_holding()
+    );
+}
@@ -0,0 +1,162 @@
+//! Makes sure that an actor that is in the middle of holding a prop can be
+//! saved to a scene and loaded again.
+#![cfg(feature = "serialize")]
+
+use std::time::Duration;
+
+use avian_pickup::{
+    ActorRng,
+    prelude::*,
+    prop::HeldProp,
+    state::{Cooldown, HoldError, Holding, NonPickupMass, PrePickupMass, PrePickupRotation},
+};
+use avian3d::prelude::*;
+use bevy::{
+    ecs::entity::EntityHashMap, prelude::*, scene::serde::SceneDeserializer,
+    time::TimeUpdateStrategy,
+};
+use serde::de::DeserializeSeed as _;
+
+const PROP_MASS: f32 = 3.0;
+
+fn setup_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        TransformPlugin,
+        AssetPlugin::default(),
+        PhysicsPlugins::default(),
+        AvianPickupPlugin::default(),
+    ))
+    .init_asset::<Mesh>()
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+        1.0 / 64.0,
+    )));
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Collider::cuboid(20.0, 1.0, 20.0),
+        Transform::from_xyz(0.0, -0.5, 0.0),
+    ));
+    app.finish();
+    app.cleanup();
+    app
+}
+
+fn save(world: &mut World, entities: [Entity; 2]) -> String {
+    let scene = DynamicSceneBuilder::from_world(world)
+        .deny_all()
+        .allow_component::<Transform>()
+        .allow_component::<RigidBody>()
+        .allow_component::<Position>()
+        .allow_component::<Rotation>()
+        .allow_component::<LinearVelocity>()
+        .allow_component::<AngularVelocity>()
+        .allow_component::<Mass>()
+        .allow_component::<AvianPickupActor>()
+        .allow_component::<AvianPickupActorState>()
+        .allow_component::<Holding>()
+        .allow_component::<Cooldown>()
+        .allow_component::<HoldError>()
+        .allow_component::<HeldProp>()
+        .allow_component::<PrePickupRotation>()
+        .allow_component::<PrePickupMass>()
+        .allow_component::<NonPickupMass>()
+        .allow_component::<ActorRng>()
+        .extract_entities(entities.into_iter())
+        .build();
+    let registry = world.resource::<AppTypeRegistry>().read();
+    scene.serialize(&registry).unwrap()
+}
+
+fn load(world: &mut World, scene: &str) -> EntityHashMap<Entity> {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let mut deserializer = ron::Deserializer::from_str(scene).unwrap();
+    let scene = SceneDeserializer {
+        type_registry: &registry.read(),
+    }
+    .deserialize(&mut deserializer)
+    .unwrap();
+    let mut entity_map = EntityHashMap::default();
+    scene.write_to_world(world, &mut entity_map).unwrap();
+    world.flush();
+    entity_map
+}
+
+#[test]
+fn actor_holding_a_prop_survives_save_and_load() {
+    let mut app = setup_app();
+    let world = app.world_mut();
+    let prop = world
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::cuboid(0.5, 0.5, 0.5),
+            Mass(PROP_MASS),
+            Transform::from_xyz(0.0, 0.25, 0.0),
+        ))
+        .id();
+    let actor = world
+        .spawn((
+            AvianPickupActor::default(),
+            ActorRng::from_seed(42),
+            Transform::from_xyz(0.0, 1.0, 3.0).looking_at(Vec3::new(0.0, 0.25, 0.0), Vec3::Y),
+        ))
+        .id();
+    for _ in 0..30 {
+        app.world_mut()
+            .write_message(AvianPickupInput::new(actor, AvianPickupAction::Pull));
+        app.update();
+    }
+    let world = app.world_mut();
+    assert!(
+        world.get::<Holding>(actor).is_some(),
+        "Failed to pick up the prop"
+    );
+    let pre_pickup_rotation = world.get::<PrePickupRotation>(prop).cloned().unwrap();
+    let pre_pickup_mass = *world.get::<PrePickupMass>(prop).unwrap();
+    let non_pickup_mass = *world.get::<NonPickupMass>(prop).unwrap();
+    let hold_error = *world.get::<HoldError>(actor).unwrap();
+    let rng = world.get::<ActorRng>(actor).cloned().unwrap();
+    let scene = save(world, [actor, prop]);
+
+    let mut app = setup_app();
+    // Make sure the loaded entities don't end up with the same IDs by accident.
+    app.world_mut()
+        .spawn_batch((0..10).map(|_| Transform::default()));
+    let entity_map = load(app.world_mut(), &scene);
+    let (new_actor, new_prop) = (entity_map[&actor], entity_map[&prop]);
+    assert_ne!(new_prop, prop);
+    let world = app.world_mut();
+    world
+        .entity_mut(new_prop)
+        .insert(Collider::cuboid(0.5, 0.5, 0.5));
+
+    assert_eq!(world.get::<Holding>(new_actor), Some(&Holding(new_prop)));
+    assert_eq!(
+        world.get::<AvianPickupActorState>(new_actor),
+        Some(&AvianPickupActorState::Holding(new_prop))
+    );
+    assert_eq!(world.get::<HoldError>(new_actor), Some(&hold_error));
+    assert_eq!(world.get::<ActorRng>(new_actor), Some(&rng));
+    assert!(world.get::<HeldProp>(new_prop).is_some());
+    assert_eq!(
+        world.get::<PrePickupRotation>(new_prop),
+        Some(&pre_pickup_rotation)
+    );
+    assert_eq!(world.get::<PrePickupMass>(new_prop), Some(&pre_pickup_mass));
+    assert_eq!(world.get::<NonPickupMass>(new_prop), Some(&non_pickup_mass));
+
+    // Keep holding for a bit, then drop the prop and see if it gets its
+    // original mass back.
+    for _ in 0..10 {
+        app.update();
+    }
+    assert!(app.world().get::<HeldProp>(new_prop).is_some());
+    for _ in 0..10 {
+        app.world_mut()
+            .write_message(AvianPickupInput::new(new_actor, AvianPickupAction::Drop));
+        app.update();
+    }
+    let world = app.world();
+    assert!(world.get::<HeldProp>(new_prop).is_none());
+    assert_eq!(world.get::<Mass>(new_prop), Some(&Mass(PROP_MASS)));
+}